@@ -1,13 +1,14 @@
 use criterion::{Criterion, criterion_group, criterion_main};
-use orx_criterion::{Experiment, Factors};
+use orx_criterion::{CachedInput, Experiment, Factors, InputCache};
 use orx_priority_queue::{DaryHeapOfIndices, PriorityQueue, PriorityQueueDecKey};
 use rand::prelude::*;
 use rand_chacha::ChaCha8Rng;
+use std::io::{Read, Write};
 
 // setup
 
-fn new_graph(num_nodes: usize, connectivity_perc: usize) -> Vec<Vec<Edge>> {
-    let mut rng = ChaCha8Rng::seed_from_u64(42);
+fn new_graph(seed: u64, num_nodes: usize, connectivity_perc: usize) -> Vec<Vec<Edge>> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
     let out_degree = num_nodes * connectivity_perc / 100;
     let out_degree_range = || out_degree.saturating_sub(100)..(out_degree + 100);
     (0..num_nodes)
@@ -34,6 +35,19 @@ struct Edge {
     cost: usize,
 }
 
+impl CachedInput for Edge {
+    fn write_to(&self, writer: &mut dyn Write) -> std::io::Result<()> {
+        self.node.write_to(writer)?;
+        self.cost.write_to(writer)
+    }
+
+    fn read_from(reader: &mut dyn Read) -> std::io::Result<Self> {
+        let node = usize::read_from(reader)?;
+        let cost = usize::read_from(reader)?;
+        Ok(Self { node, cost })
+    }
+}
+
 fn shortest_path<const D: usize>(
     adj_list: &[Vec<Edge>],
     start: usize,
@@ -110,8 +124,25 @@ impl Experiment for ShortestPathExp {
 
     type Output = Option<usize>;
 
+    fn master_seed(&self) -> u64 {
+        42
+    }
+
+    fn input_cache(&self) -> Option<InputCache<Self::Input>> {
+        Some(InputCache::new("1"))
+    }
+
     fn input(&mut self, data: &Self::InputFactors) -> Self::Input {
-        new_graph(data.num_nodes, data.connectivity_perc)
+        new_graph(
+            self.input_seed(data),
+            data.num_nodes,
+            data.connectivity_perc,
+        )
+    }
+
+    fn input_size_bytes(&self, input: &Self::Input) -> Option<usize> {
+        let edges: usize = input.iter().map(|x| x.len()).sum();
+        Some(input.len() * size_of::<Vec<Edge>>() + edges * size_of::<Edge>())
     }
 
     fn execute(&mut self, variant: &Self::AlgFactors, input: &Self::Input) -> Self::Output {
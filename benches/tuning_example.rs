@@ -1,5 +1,6 @@
-use criterion::{Criterion, criterion_group, criterion_main};
-use orx_criterion::{Experiment, Factors};
+use criterion::{Criterion, SamplingMode, Throughput, criterion_group, criterion_main};
+use orx_criterion::{Experiment, Factors, TreatmentConfig};
+use std::time::Duration;
 
 // Input Factors
 
@@ -171,6 +172,32 @@ impl Experiment for SearchExp {
         Some(input.position)
     }
 
+    fn throughput(&self, input_levels: &Self::InputFactors) -> Option<Throughput> {
+        // each execution searches over all elements of the array in the worst case
+        Some(Throughput::Elements(input_levels.len as u64))
+    }
+
+    fn configure(
+        &self,
+        input_levels: &Self::InputFactors,
+        _alg_variant: &Self::AlgFactors,
+    ) -> TreatmentConfig {
+        // long arrays require longer measurement times and flat sampling,
+        // while the defaults are sufficient for short arrays
+        match input_levels.len {
+            len if len >= 1 << 20 => TreatmentConfig {
+                measurement_time: Some(Duration::from_secs(20)),
+                sampling_mode: Some(SamplingMode::Flat),
+                ..Default::default()
+            },
+            _ => TreatmentConfig {
+                measurement_time: Some(Duration::from_secs(5)),
+                sampling_mode: Some(SamplingMode::Auto),
+                ..Default::default()
+            },
+        }
+    }
+
     fn validate_output(
         &self,
         _settings: &Self::InputFactors,
@@ -1,9 +1,16 @@
 use criterion::{Criterion, criterion_group, criterion_main};
-use orx_criterion::{Experiment, Factors};
+use orx_criterion::{Experiment, Factors, PhaseTimer};
 use rand::prelude::*;
 use rand_chacha::ChaCha8Rng;
 use std::collections::{BTreeMap, HashMap};
 
+// allocations of each store type are recorded and added to the summary by the counting allocator;
+// counting adds an overhead to every allocation; hence, the times are not comparable to a run without it
+
+#[global_allocator]
+static ALLOCATOR: orx_criterion::CountingAllocator =
+    orx_criterion::CountingAllocator::new(std::alloc::System);
+
 // https://leetcode.com/problems/two-sum/description/
 
 // data
@@ -108,8 +115,15 @@ impl IndexOf<'_> for BTreeMap<i64, usize> {
 
 // generic algorithm
 
-fn algorithm<'a, S: IndexOf<'a>>(array: &'a [i64], target: i64) -> Option<[usize; 2]> {
+fn algorithm<'a, S: IndexOf<'a>>(
+    array: &'a [i64],
+    target: i64,
+    timer: &mut PhaseTimer,
+) -> Option<[usize; 2]> {
+    timer.start("build");
     let store = S::from_array(array);
+
+    timer.start("query");
     for (i, a) in array.iter().enumerate() {
         let b = target - a;
         if let Some(j) = store.index_of(b) {
@@ -137,8 +151,12 @@ impl Experiment for TwoSumExp {
 
     type Output = Option<[usize; 2]>;
 
+    fn master_seed(&self) -> u64 {
+        42
+    }
+
     fn input(&mut self, data: &Self::InputFactors) -> Self::Input {
-        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let mut rng = ChaCha8Rng::seed_from_u64(self.input_seed(data));
         let n = data.0;
         let mut array: Vec<_> = (0..data.0).map(|_| rng.random_range(3..n as i64)).collect();
         let i = n / 2;
@@ -150,15 +168,29 @@ impl Experiment for TwoSumExp {
     }
 
     fn execute(&mut self, variant: &Self::AlgFactors, input: &Self::Input) -> Self::Output {
+        // the default timer is disabled and does not add to the allocations of the store types
+        self.execute_phased(variant, input, &mut PhaseTimer::default())
+    }
+
+    fn execute_phased(
+        &mut self,
+        variant: &Self::AlgFactors,
+        input: &Self::Input,
+        timer: &mut PhaseTimer,
+    ) -> Self::Output {
         let array = &input.array;
         match variant.0 {
-            StoreType::None => algorithm::<&[i64]>(array, 3),
-            StoreType::SortedVec => algorithm::<SortedVec>(array, 3),
-            StoreType::HashMap => algorithm::<HashMap<_, _>>(array, 3),
-            StoreType::BTreeMap => algorithm::<BTreeMap<_, _>>(array, 3),
+            StoreType::None => algorithm::<&[i64]>(array, 3, timer),
+            StoreType::SortedVec => algorithm::<SortedVec>(array, 3, timer),
+            StoreType::HashMap => algorithm::<HashMap<_, _>>(array, 3, timer),
+            StoreType::BTreeMap => algorithm::<BTreeMap<_, _>>(array, 3, timer),
         }
     }
 
+    fn phases(&self) -> Vec<&'static str> {
+        vec!["build", "query"]
+    }
+
     fn validate_output(&self, _: &Self::InputFactors, input: &Self::Input, output: &Self::Output) {
         assert_eq!(input.indices, *output);
         assert_eq!(
@@ -0,0 +1,156 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// A global allocator wrapper which counts the allocations made through the wrapped allocator.
+///
+/// When it is registered as the global allocator of the benchmark, each treatment of the experiment
+/// additionally records the following for one `execute` call:
+///
+/// * number of allocations,
+/// * total number of bytes allocated, and
+/// * peak number of live bytes allocated during the call.
+///
+/// These values are then shown as additional columns of the summary tables.
+///
+/// Note that counting adds a small overhead to every allocation, including those in the measured runs.
+/// Therefore, it should be registered only when allocations are of interest.
+///
+/// # Examples
+///
+/// The allocator is registered with the `#[global_allocator]` attribute, typically at the top of the
+/// benchmark file.
+///
+/// ```
+/// use orx_criterion::*;
+/// use std::alloc::System;
+///
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator::new(System);
+///
+/// let (vec, stats) = measure_allocations(|| {
+///     let mut vec = Vec::with_capacity(4);
+///     vec.extend([1u64, 2, 3, 4]);
+///     vec
+/// });
+///
+/// assert_eq!(vec, [1, 2, 3, 4]);
+/// assert_eq!(
+///     stats,
+///     Some(AllocationStats {
+///         count: 1,
+///         bytes: 32,
+///         peak_bytes: 32
+///     })
+/// );
+/// ```
+pub struct CountingAllocator<A = System> {
+    inner: A,
+}
+
+impl<A> CountingAllocator<A> {
+    /// Creates a counting allocator wrapping the `inner` allocator.
+    pub const fn new(inner: A) -> Self {
+        Self { inner }
+    }
+}
+
+fn on_alloc(size: usize) {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        INSTALLED.store(true, Ordering::Relaxed);
+    }
+    COUNT.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size, Ordering::Relaxed);
+    let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+fn on_dealloc(size: usize) {
+    // memory allocated before the measurement, such as an owned input, might be freed during the measurement;
+    // live bytes are floored at zero so that these frees do not offset the allocations being measured
+    let _ = LIVE.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |live| {
+        Some(live.saturating_sub(size))
+    });
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: the caller upholds the contract of GlobalAlloc::alloc
+        let ptr = unsafe { self.inner.alloc(layout) };
+        if !ptr.is_null() {
+            on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: the caller upholds the contract of GlobalAlloc::dealloc
+        unsafe { self.inner.dealloc(ptr, layout) };
+        on_dealloc(layout.size());
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: the caller upholds the contract of GlobalAlloc::alloc_zeroed
+        let ptr = unsafe { self.inner.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // SAFETY: the caller upholds the contract of GlobalAlloc::realloc
+        let new_ptr = unsafe { self.inner.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            on_dealloc(layout.size());
+            on_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Allocations made during a function call, as measured by [`measure_allocations`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AllocationStats {
+    /// Number of allocations, where each reallocation counts as an allocation.
+    pub count: usize,
+    /// Total number of bytes allocated.
+    pub bytes: usize,
+    /// Peak number of bytes allocated and not yet deallocated at any point during the call.
+    pub peak_bytes: usize,
+}
+
+/// Calls `f` and returns its output together with the allocations made during the call.
+///
+/// Allocation statistics are available only if the [`CountingAllocator`] is registered as the global allocator;
+/// `None` is returned otherwise.
+///
+/// Since the allocator cannot tell whether a freed block is allocated before or during the call, live bytes of
+/// the call are floored at zero; hence, freeing memory allocated before the call, such as dropping an owned
+/// input, does not drive them negative and hide the allocations made afterwards.
+///
+/// Note that the counters are global; hence, allocations of all threads during the call are counted.
+pub fn measure_allocations<T>(f: impl FnOnce() -> T) -> (T, Option<AllocationStats>) {
+    COUNT.store(0, Ordering::Relaxed);
+    BYTES.store(0, Ordering::Relaxed);
+    LIVE.store(0, Ordering::Relaxed);
+    PEAK.store(0, Ordering::Relaxed);
+
+    let output = f();
+
+    let stats = AllocationStats {
+        count: COUNT.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+        peak_bytes: PEAK.load(Ordering::Relaxed),
+    };
+
+    match INSTALLED.load(Ordering::Relaxed) {
+        true => (output, Some(stats)),
+        false => (output, None),
+    }
+}
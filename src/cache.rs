@@ -0,0 +1,317 @@
+use crate::experiment_sealed::ExperimentSealed;
+use crate::summary::create_file;
+use crate::{Experiment, Replicate};
+use colorize::AnsiColor;
+use std::fs::{File, rename};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::Path;
+
+/// First bytes of every input cache file.
+const MAGIC: &[u8] = b"orx-criterion-input-cache\n";
+
+/// An input which can be written to and read back from the on-disk input cache.
+///
+/// The trait is implemented for primitive numbers, `bool`, `String`, vectors and tuples of cached inputs.
+/// Therefore, it is often sufficient to implement it for the element type of the input.
+///
+/// See [`InputCache`] for details of caching.
+///
+/// # Examples
+///
+/// ```
+/// use orx_criterion::*;
+/// use std::io::{Read, Write};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Edge {
+///     node: usize,
+///     cost: u64,
+/// }
+///
+/// impl CachedInput for Edge {
+///     fn write_to(&self, writer: &mut dyn Write) -> std::io::Result<()> {
+///         self.node.write_to(writer)?;
+///         self.cost.write_to(writer)
+///     }
+///
+///     fn read_from(reader: &mut dyn Read) -> std::io::Result<Self> {
+///         let node = usize::read_from(reader)?;
+///         let cost = u64::read_from(reader)?;
+///         Ok(Self { node, cost })
+///     }
+/// }
+///
+/// let graph = vec![vec![Edge { node: 1, cost: 10 }], vec![]];
+///
+/// let mut bytes = vec![];
+/// graph.write_to(&mut bytes).unwrap();
+/// let loaded = Vec::<Vec<Edge>>::read_from(&mut bytes.as_slice()).unwrap();
+/// assert_eq!(loaded, graph);
+/// ```
+pub trait CachedInput: Sized {
+    /// Writes the input to the `writer`.
+    fn write_to(&self, writer: &mut dyn Write) -> std::io::Result<()>;
+
+    /// Reads the input from the `reader` which is written by [`write_to`].
+    ///
+    /// [`write_to`]: CachedInput::write_to
+    fn read_from(reader: &mut dyn Read) -> std::io::Result<Self>;
+}
+
+macro_rules! impl_cached_number {
+    ($($t:ty),*) => {
+        $(
+            impl CachedInput for $t {
+                fn write_to(&self, writer: &mut dyn Write) -> std::io::Result<()> {
+                    writer.write_all(&self.to_le_bytes())
+                }
+
+                fn read_from(reader: &mut dyn Read) -> std::io::Result<Self> {
+                    let mut bytes = [0u8; size_of::<$t>()];
+                    reader.read_exact(&mut bytes)?;
+                    Ok(<$t>::from_le_bytes(bytes))
+                }
+            }
+        )*
+    };
+}
+
+impl_cached_number!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl CachedInput for usize {
+    fn write_to(&self, writer: &mut dyn Write) -> std::io::Result<()> {
+        (*self as u64).write_to(writer)
+    }
+
+    fn read_from(reader: &mut dyn Read) -> std::io::Result<Self> {
+        usize::try_from(u64::read_from(reader)?)
+            .map_err(|e| std::io::Error::new(ErrorKind::InvalidData, e))
+    }
+}
+
+impl CachedInput for isize {
+    fn write_to(&self, writer: &mut dyn Write) -> std::io::Result<()> {
+        (*self as i64).write_to(writer)
+    }
+
+    fn read_from(reader: &mut dyn Read) -> std::io::Result<Self> {
+        isize::try_from(i64::read_from(reader)?)
+            .map_err(|e| std::io::Error::new(ErrorKind::InvalidData, e))
+    }
+}
+
+impl CachedInput for bool {
+    fn write_to(&self, writer: &mut dyn Write) -> std::io::Result<()> {
+        u8::from(*self).write_to(writer)
+    }
+
+    fn read_from(reader: &mut dyn Read) -> std::io::Result<Self> {
+        match u8::read_from(reader)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            x => Err(std::io::Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid bool {x}"),
+            )),
+        }
+    }
+}
+
+impl CachedInput for String {
+    fn write_to(&self, writer: &mut dyn Write) -> std::io::Result<()> {
+        self.len().write_to(writer)?;
+        writer.write_all(self.as_bytes())
+    }
+
+    fn read_from(reader: &mut dyn Read) -> std::io::Result<Self> {
+        let len = usize::read_from(reader)?;
+        let mut bytes = vec![];
+        reader.take(len as u64).read_to_end(&mut bytes)?;
+        match bytes.len() == len {
+            true => {
+                String::from_utf8(bytes).map_err(|e| std::io::Error::new(ErrorKind::InvalidData, e))
+            }
+            false => Err(ErrorKind::UnexpectedEof.into()),
+        }
+    }
+}
+
+impl<T: CachedInput> CachedInput for Vec<T> {
+    fn write_to(&self, writer: &mut dyn Write) -> std::io::Result<()> {
+        self.len().write_to(writer)?;
+        self.iter().try_for_each(|x| x.write_to(writer))
+    }
+
+    fn read_from(reader: &mut dyn Read) -> std::io::Result<Self> {
+        let len = usize::read_from(reader)?;
+        (0..len).map(|_| T::read_from(reader)).collect()
+    }
+}
+
+impl<A: CachedInput, B: CachedInput> CachedInput for (A, B) {
+    fn write_to(&self, writer: &mut dyn Write) -> std::io::Result<()> {
+        self.0.write_to(writer)?;
+        self.1.write_to(writer)
+    }
+
+    fn read_from(reader: &mut dyn Read) -> std::io::Result<Self> {
+        Ok((A::read_from(reader)?, B::read_from(reader)?))
+    }
+}
+
+impl<A: CachedInput, B: CachedInput, C: CachedInput> CachedInput for (A, B, C) {
+    fn write_to(&self, writer: &mut dyn Write) -> std::io::Result<()> {
+        self.0.write_to(writer)?;
+        self.1.write_to(writer)?;
+        self.2.write_to(writer)
+    }
+
+    fn read_from(reader: &mut dyn Read) -> std::io::Result<Self> {
+        Ok((
+            A::read_from(reader)?,
+            B::read_from(reader)?,
+            C::read_from(reader)?,
+        ))
+    }
+}
+
+/// On-disk cache of the inputs of an experiment, which is enabled by [`input_cache`].
+///
+/// When enabled, each instance of an input is written to a file under the
+/// "target/criterion/{name}/inputs" directory once it is created by [`input_replicate`] or [`input`].
+/// In the subsequent runs, the input is read from this file instead of being created again.
+///
+/// A cached input is identified by the [`key_long`] of its input factors, its replicate, its seed derived from
+/// the [`master_seed`] and the `version` of the cache.
+/// The inputs are created again whenever any of these change.
+/// Therefore, the version must be changed whenever the way that the inputs are created changes.
+///
+/// [`input_cache`]: crate::Experiment::input_cache
+/// [`input_replicate`]: crate::Experiment::input_replicate
+/// [`input`]: crate::Experiment::input
+/// [`key_long`]: crate::Factors::key_long
+/// [`master_seed`]: crate::Experiment::master_seed
+pub struct InputCache<I> {
+    version: String,
+    write: fn(&I, &mut dyn Write) -> std::io::Result<()>,
+    read: fn(&mut dyn Read) -> std::io::Result<I>,
+}
+
+impl<I: CachedInput> InputCache<I> {
+    /// Creates an input cache with the given `version`.
+    pub fn new(version: impl Into<String>) -> Self {
+        Self {
+            version: version.into(),
+            write: I::write_to,
+            read: I::read_from,
+        }
+    }
+}
+
+impl<I> InputCache<I> {
+    /// Version of the cache; cached inputs with a different version are ignored.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Writes the header identifying the cached input to the `writer`.
+    fn write_header(&self, writer: &mut dyn Write, key: &str, seed: u64) -> std::io::Result<()> {
+        writer.write_all(MAGIC)?;
+        self.version.clone().write_to(writer)?;
+        key.to_string().write_to(writer)?;
+        seed.write_to(writer)
+    }
+
+    /// Reads the header from the `reader` and returns whether or not it identifies the input with the given
+    /// `key` and `seed` of this version.
+    fn read_header(&self, reader: &mut dyn Read, key: &str, seed: u64) -> std::io::Result<bool> {
+        let mut magic = vec![0u8; MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        Ok(magic == MAGIC
+            && String::read_from(reader)? == self.version
+            && String::read_from(reader)? == key
+            && u64::read_from(reader)? == seed)
+    }
+
+    /// Loads the input with the given `key` and `seed` from the file at `path`; returns None if the file does
+    /// not exist or it belongs to another input or version.
+    pub(crate) fn load(&self, path: &Path, key: &str, seed: u64) -> std::io::Result<Option<I>> {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let mut reader = BufReader::new(file);
+        match self.read_header(&mut reader, key, seed)? {
+            true => (self.read)(&mut reader).map(Some),
+            false => Ok(None),
+        }
+    }
+
+    /// Stores the `input` with the given `key` and `seed` to the file at `path`.
+    ///
+    /// The input is first written to a temporary file which is then renamed, so that an interrupted run does
+    /// not leave a partially written cache file.
+    pub(crate) fn store(
+        &self,
+        path: &Path,
+        key: &str,
+        seed: u64,
+        input: &I,
+    ) -> std::io::Result<()> {
+        let tmp_path = path.with_extension("tmp");
+        let mut writer = BufWriter::new(create_file(&tmp_path)?);
+        self.write_header(&mut writer, key, seed)?;
+        (self.write)(input, &mut writer)?;
+        writer.flush()?;
+        drop(writer);
+        rename(tmp_path, path)
+    }
+}
+
+/// Creates the `replicate`-th instance of the input defined by the `input_variant`.
+///
+/// The input is loaded from the cache when [`input_cache`] is enabled and the input is already cached;
+/// otherwise, it is created by [`input_replicate`] and stored in the cache if enabled.
+///
+/// [`input_cache`]: crate::Experiment::input_cache
+/// [`input_replicate`]: crate::Experiment::input_replicate
+pub(crate) fn create_input<E: Experiment>(
+    exp: &mut E,
+    name: &str,
+    input_variant: &E::InputFactors,
+    replicate: usize,
+) -> E::Input {
+    let replicate = Replicate::new(exp.input_seed(input_variant), replicate);
+    let Some(cache) = exp.input_cache() else {
+        return exp.input_replicate(input_variant, replicate);
+    };
+
+    let path = exp.input_cache_path(name, input_variant, replicate.index);
+    let key = exp.input_instance_key_long(input_variant, replicate.index);
+    match cache.load(&path, &key, replicate.seed) {
+        Ok(Some(input)) => {
+            let log = format!("Input is loaded from the cache at {path:?}");
+            println!("{}", log.italic());
+            return input;
+        }
+        Ok(None) => {}
+        Err(e) => {
+            let log = format!("Failed to load the input from the cache at {path:?}: {e}");
+            println!("{}", log.red());
+        }
+    }
+
+    let input = exp.input_replicate(input_variant, replicate);
+    match cache.store(&path, &key, replicate.seed, &input) {
+        Ok(()) => {
+            let log = format!("Input is stored in the cache at {path:?}");
+            println!("{}", log.italic());
+        }
+        Err(e) => {
+            let log = format!("Failed to store the input in the cache at {path:?}: {e}");
+            println!("{}", log.red());
+        }
+    }
+    input
+}
@@ -0,0 +1,143 @@
+use criterion::measurement::Measurement;
+use criterion::{BenchmarkGroup, SamplingMode};
+use std::time::Duration;
+
+/// Configuration of a single treatment, returned by [`configure`].
+///
+/// Except for the `time_limit`, the fields define the criterion settings of the treatment.
+/// Each of these fields which is `None` is not changed, and hence, uses the configuration of the criterion
+/// benchmark runner.
+/// Note that criterion cannot reset a setting of a benchmark group once it is set.
+/// Therefore, a value set for a treatment is kept for the subsequent treatments of the same benchmark group
+/// unless they set it again.
+/// It is recommended to return the fields that are set for any treatment for all treatments.
+///
+/// Settings of the criterion runner which do not apply to a benchmark group, such as the plotting backend,
+/// cannot be set per treatment; they are configured on the [`Criterion`] passed to [`bench`].
+///
+/// Invalid values, such as a sample size less than 10, are rejected by [`try_bench`] with the
+/// [`Error::InvalidTreatmentConfig`] error before any treatment is run.
+///
+/// [`Criterion`]: criterion::Criterion
+/// [`bench`]: crate::Experiment::bench
+/// [`try_bench`]: crate::Experiment::try_bench
+/// [`Error::InvalidTreatmentConfig`]: crate::Error::InvalidTreatmentConfig
+/// [`configure`]: crate::Experiment::configure
+///
+/// # Examples
+///
+/// ```
+/// use orx_criterion::*;
+/// use criterion::SamplingMode;
+/// use std::time::Duration;
+///
+/// let long_running = TreatmentConfig {
+///     measurement_time: Some(Duration::from_secs(30)),
+///     sampling_mode: Some(SamplingMode::Flat),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(long_running.sample_size, None);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TreatmentConfig {
+    /// Number of samples to collect; must be at least 10.
+    pub sample_size: Option<usize>,
+    /// Time to warm up before the measurement; must be positive.
+    pub warm_up_time: Option<Duration>,
+    /// Target time to spend for the measurement; must be positive.
+    pub measurement_time: Option<Duration>,
+    /// Sampling mode of the measurement.
+    pub sampling_mode: Option<SamplingMode>,
+    /// Noise threshold below which changes from the previous run are considered as noise; must not be negative.
+    pub noise_threshold: Option<f64>,
+    /// Number of bootstrap resamples used in the statistical analysis; must be positive.
+    pub nresamples: Option<usize>,
+    /// Confidence level of the confidence intervals of the estimates; must be between 0 and 1.
+    pub confidence_level: Option<f64>,
+    /// Significance level of the hypothesis tests comparing to the previous run; must be between 0 and 1.
+    pub significance_level: Option<f64>,
+    /// Time limit of a single execution of the treatment.
+    ///
+    /// When set, the treatment is executed once as a pilot run before the measurement.
+    /// If the pilot execution takes longer than the limit, the treatment is marked as `TIMEOUT` in the summary
+    /// tables and criterion measurement is skipped.
+    /// Note that the pilot execution is not interrupted; the limit prevents the lengthy measurement of the
+    /// pathologically slow treatments.
+    pub time_limit: Option<Duration>,
+}
+
+impl TreatmentConfig {
+    /// Returns the reason why the configuration is rejected by criterion, if any.
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        let is_level = |x: f64| x > 0.0 && x < 1.0;
+        let invalid = [
+            (
+                self.sample_size.is_some_and(|x| x < 10),
+                "sample size must be at least 10",
+            ),
+            (
+                self.warm_up_time.is_some_and(|x| x.is_zero()),
+                "warm-up time must be positive",
+            ),
+            (
+                self.measurement_time.is_some_and(|x| x.is_zero()),
+                "measurement time must be positive",
+            ),
+            (
+                self.noise_threshold.is_some_and(|x| x.is_nan() || x < 0.0),
+                "noise threshold must not be negative",
+            ),
+            (
+                self.nresamples.is_some_and(|x| x == 0),
+                "number of resamples must be positive",
+            ),
+            (
+                self.confidence_level.is_some_and(|x| !is_level(x)),
+                "confidence level must be between 0 and 1",
+            ),
+            (
+                self.significance_level.is_some_and(|x| !is_level(x)),
+                "significance level must be between 0 and 1",
+            ),
+        ];
+        match invalid.into_iter().find(|x| x.0) {
+            Some((_, reason)) => Err(reason.to_string()),
+            None => Ok(()),
+        }
+    }
+
+    /// Applies the configuration to the benchmark `group` which is about to run the treatment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the configuration is not [`validate`]d, such as when the sample size is less than 10.
+    ///
+    /// [`validate`]: TreatmentConfig::validate
+    pub(crate) fn apply<M: Measurement>(&self, group: &mut BenchmarkGroup<'_, M>) {
+        if let Some(x) = self.sample_size {
+            group.sample_size(x);
+        }
+        if let Some(x) = self.warm_up_time {
+            group.warm_up_time(x);
+        }
+        if let Some(x) = self.measurement_time {
+            group.measurement_time(x);
+        }
+        if let Some(x) = self.sampling_mode {
+            group.sampling_mode(x);
+        }
+        if let Some(x) = self.noise_threshold {
+            group.noise_threshold(x);
+        }
+        if let Some(x) = self.nresamples {
+            group.nresamples(x);
+        }
+        if let Some(x) = self.confidence_level {
+            group.confidence_level(x);
+        }
+        if let Some(x) = self.significance_level {
+            group.significance_level(x);
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use crate::Error;
+
+/// Format of the csv files created by the experiment, which is determined by [`csv_format`].
+///
+/// Fields are quoted as defined by RFC 4180: a field containing the delimiter, a double quote or a line break is
+/// enclosed in double quotes, and each double quote within the field is escaped by another double quote.
+/// Therefore, factor levels such as `(1, "a")` can safely be written to the csv files.
+///
+/// [`csv_format`]: crate::Experiment::csv_format
+///
+/// # Examples
+///
+/// ```
+/// use orx_criterion::CsvFormat;
+///
+/// // semicolon separated summary together with its long format
+/// let format = CsvFormat {
+///     delimiter: ';',
+///     long_format: true,
+/// };
+///
+/// // default format: comma separated summary in the wide format
+/// let format = CsvFormat::default();
+/// assert_eq!(format.delimiter, ',');
+/// assert!(!format.long_format);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvFormat {
+    /// Delimiter separating the fields of a row, which is a comma by default.
+    ///
+    /// It must not be a double quote or a line break; otherwise, the experiment fails with the
+    /// [`Error::InvalidCsvDelimiter`] error before running any treatment.
+    ///
+    /// [`Error::InvalidCsvDelimiter`]: crate::Error::InvalidCsvDelimiter
+    pub delimiter: char,
+    /// Whether or not to additionally write the summary in the long format to the "summary_long_{name}.csv"
+    /// file, which is false by default.
+    ///
+    /// In the long format, each row holds a single statistic of a treatment, such as its time or a metric,
+    /// identified by the index and factor levels of the treatment together with the name of the statistic.
+    pub long_format: bool,
+}
+
+impl Default for CsvFormat {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            long_format: false,
+        }
+    }
+}
+
+impl CsvFormat {
+    /// Returns an error if the delimiter is a double quote or a line break, which cannot be distinguished from
+    /// the quoting of the fields and the line breaks of the rows.
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        match self.delimiter {
+            '"' | '\n' | '\r' => Err(Error::InvalidCsvDelimiter(self.delimiter)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Creates the line of the csv row with the given `fields`, including the line break.
+    pub(crate) fn row<S: AsRef<str>>(&self, fields: &[S]) -> String {
+        let mut line = String::new();
+        for (idx, field) in fields.iter().enumerate() {
+            if idx > 0 {
+                line.push(self.delimiter);
+            }
+            line.push_str(&self.quote(field.as_ref()));
+        }
+        line.push('\n');
+        line
+    }
+
+    /// Quotes the `field` if it contains the delimiter, a double quote or a line break.
+    pub(crate) fn quote(&self, field: &str) -> String {
+        let requires_quotes = field
+            .chars()
+            .any(|c| c == self.delimiter || matches!(c, '"' | '\n' | '\r'));
+        match requires_quotes {
+            true => format!("\"{}\"", field.replace('"', "\"\"")),
+            false => field.to_string(),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+/// Error of an experiment returned by [`try_bench`].
+///
+/// Errors are raised while reporting the results of the experiment, such as writing the summary files or
+/// printing the summary table, or before running the experiment when its definition is inconsistent.
+/// Note that mismatches of the outputs of the algorithm variants are not reported by errors; they are
+/// assertion failures of the benchmark.
+///
+/// [`try_bench`]: crate::Experiment::try_bench
+#[derive(Debug)]
+pub enum Error {
+    /// A file of the experiment, such as the summary csv, could not be written.
+    Write {
+        /// Path of the file.
+        path: PathBuf,
+        /// Underlying io error.
+        source: std::io::Error,
+    },
+    /// A table could not be printed to the standard output.
+    Print(std::io::Error),
+    /// [`throughput`] is defined for some of the inputs but not for the others.
+    ///
+    /// [`throughput`]: crate::Experiment::throughput
+    InconsistentThroughput {
+        /// Long key of an input with a throughput.
+        defined: String,
+        /// Long key of an input without a throughput.
+        missing: String,
+    },
+    /// Delimiter of the [`CsvFormat`] is a double quote or a line break, which cannot separate the fields.
+    ///
+    /// [`CsvFormat`]: crate::CsvFormat
+    InvalidCsvDelimiter(char),
+    /// [`num_replicates`] is zero, while each input requires at least one instance.
+    ///
+    /// [`num_replicates`]: crate::Experiment::num_replicates
+    ZeroReplicates,
+    /// [`TreatmentConfig`] returned by [`configure`] for a treatment has a value which criterion rejects, such as
+    /// a sample size less than 10.
+    ///
+    /// [`TreatmentConfig`]: crate::TreatmentConfig
+    /// [`configure`]: crate::Experiment::configure
+    InvalidTreatmentConfig {
+        /// Long key of the treatment.
+        treatment: String,
+        /// Reason why the configuration is rejected.
+        reason: String,
+    },
+    /// Input factor of the [`Registration::Parameterized`] registration is not one of the input factors.
+    ///
+    /// [`Registration::Parameterized`]: crate::Registration::Parameterized
+    UnknownInputFactor {
+        /// Input factor of the registration.
+        input_factor: &'static str,
+        /// Names of the input factors.
+        factor_names: Vec<&'static str>,
+    },
+}
+
+impl Error {
+    /// Creates a function mapping an io error to the [`Error::Write`] error of the file at `path`.
+    pub(crate) fn write(path: PathBuf) -> impl FnOnce(std::io::Error) -> Self {
+        |source| Self::Write { path, source }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Write { path, source } => write!(f, "failed to write {path:?}: {source}"),
+            Self::Print(source) => write!(f, "failed to print to the standard output: {source}"),
+            Self::InconsistentThroughput { defined, missing } => write!(
+                f,
+                "throughput must be defined for all or none of the inputs; it is defined for {defined} but not for {missing}"
+            ),
+            Self::InvalidCsvDelimiter(delimiter) => write!(
+                f,
+                "csv delimiter must not be a double quote or a line break, but it is {delimiter:?}"
+            ),
+            Self::ZeroReplicates => write!(f, "number of replicates must be at least one"),
+            Self::InvalidTreatmentConfig { treatment, reason } => {
+                write!(
+                    f,
+                    "invalid configuration of the treatment {treatment}: {reason}"
+                )
+            }
+            Self::UnknownInputFactor {
+                input_factor,
+                factor_names,
+            } => write!(
+                f,
+                "input factor {input_factor:?} of the parameterized registration is not one of the input factors {factor_names:?}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Write { source, .. } => Some(source),
+            Self::Print(source) => Some(source),
+            Self::InconsistentThroughput { .. }
+            | Self::InvalidCsvDelimiter(_)
+            | Self::ZeroReplicates
+            | Self::InvalidTreatmentConfig { .. }
+            | Self::UnknownInputFactor { .. } => None,
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Time estimate of a treatment together with its confidence interval, as computed by criterion.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Estimate {
+    /// Statistic of the estimate, which is "slope" when criterion fits a slope to the samples and "mean"
+    /// otherwise, such as with the flat sampling mode.
+    pub statistic: &'static str,
+    /// Point estimate of the time.
+    pub point: f64,
+    /// Lower bound of the confidence interval.
+    pub lower_bound: f64,
+    /// Upper bound of the confidence interval.
+    pub upper_bound: f64,
+    /// Confidence level of the interval, such as 0.95.
+    pub confidence_level: f64,
+}
+
+impl Estimate {
+    /// Reads the estimate from the "estimates.json" file created by criterion at `path`; returns None if the
+    /// file does not exist or cannot be parsed.
+    pub fn read(path: &Path) -> Option<Self> {
+        let mut file = File::open(path).ok()?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents).ok()?;
+        Self::parse(&contents)
+    }
+
+    /// Parses the estimate from the `contents` of an "estimates.json" file.
+    pub fn parse(contents: &str) -> Option<Self> {
+        let field_slope_null = "\"slope\":null";
+        let is_slope_null = contents.contains(field_slope_null);
+
+        let statistic = match is_slope_null {
+            true => "mean",
+            false => "slope",
+        };
+        let field = format!("\"{statistic}\"");
+        let position = contents.find(&field)?;
+        let slice = &contents[position + field.len()..];
+
+        Some(Self {
+            statistic,
+            point: number_field(slice, "point_estimate")?,
+            lower_bound: number_field(slice, "lower_bound")?,
+            upper_bound: number_field(slice, "upper_bound")?,
+            confidence_level: number_field(slice, "confidence_level")?,
+        })
+    }
+
+    /// Aggregates the estimates of the replicates of a treatment, where the point estimate is their mean and
+    /// the interval is the envelope of their confidence intervals; None if there are no estimates.
+    pub fn aggregate(estimates: &[Self]) -> Option<Self> {
+        let first = estimates.first()?;
+        let fold = |init: f64, f: fn(f64, f64) -> f64, x: fn(&Self) -> f64| {
+            estimates.iter().map(x).fold(init, f)
+        };
+        Some(Self {
+            statistic: first.statistic,
+            point: fold(0.0, |x, y| x + y, |x| x.point) / estimates.len() as f64,
+            lower_bound: fold(f64::INFINITY, f64::min, |x| x.lower_bound),
+            upper_bound: fold(f64::NEG_INFINITY, f64::max, |x| x.upper_bound),
+            confidence_level: first.confidence_level,
+        })
+    }
+}
+
+/// Value of the first numeric field with the given `name` in the json `slice`.
+fn number_field(slice: &str, name: &str) -> Option<f64> {
+    let field = format!("\"{name}\":");
+    let position = slice.find(&field)?;
+    let slice = &slice[position + field.len()..];
+    let end = slice.find([',', '}'])?;
+    slice[..end].trim().parse().ok()
+}
+
+/// Aggregates the estimates of the treatments on each instance of the inputs into the estimates of the
+/// treatments on each input; see [`Estimate::aggregate`].
+pub(crate) fn aggregate_estimates(
+    num_replicates: usize,
+    estimates: &[Vec<Option<Estimate>>],
+) -> Vec<Vec<Option<Estimate>>> {
+    estimates
+        .chunks(num_replicates)
+        .map(|replicates| {
+            let num_a = replicates.first().map(|x| x.len()).unwrap_or(0);
+            (0..num_a)
+                .map(|a| {
+                    let values: Vec<_> = replicates.iter().filter_map(|x| x[a]).collect();
+                    Estimate::aggregate(&values)
+                })
+                .collect()
+        })
+        .collect()
+}
@@ -0,0 +1,30 @@
+use criterion::BatchSize;
+
+/// Defines how the algorithm variants of an experiment access their input while being measured.
+///
+/// It is determined by [`execution_mode`] of the experiment.
+///
+/// [`execution_mode`]: crate::Experiment::execution_mode
+#[derive(Debug, Clone)]
+pub enum ExecutionMode<I> {
+    /// Algorithm variants are executed by [`execute`] with a reference to the input.
+    ///
+    /// This is the default mode.
+    ///
+    /// [`execute`]: crate::Experiment::execute
+    Borrowed,
+    /// Algorithm variants consume or mutate their input, and they are executed by [`execute_mut`] with an owned
+    /// copy of the input.
+    ///
+    /// Copies are created by the `setup` function, such as `Clone::clone`, in batches of the given `batch_size`
+    /// using criterion's `iter_batched`.
+    /// Time spent for creating the copies is excluded from the measurement.
+    ///
+    /// [`execute_mut`]: crate::Experiment::execute_mut
+    Consumed {
+        /// Creates an owned copy of the input to be consumed by a single execution.
+        setup: fn(&I) -> I,
+        /// Size of the batches of the input copies.
+        batch_size: BatchSize,
+    },
+}
@@ -1,9 +1,21 @@
-use crate::Factors;
+use crate::cache::create_input;
 use crate::experiment_sealed::ExperimentSealed;
-use crate::summary::summarize;
+use crate::hooks::{log_hook, release_inputs};
+use crate::order::create_order_csv;
+use crate::plan::is_plan_mode;
+use crate::record::{InputRecord, TreatmentRecord, TreatmentStatus, record_treatment};
+use crate::summary::try_summarize;
+use crate::throughput::input_throughputs;
+use crate::{
+    CsvFormat, Error, ExecutionMode, ExperimentMeasurement, ExperimentPlan, ExperimentResult,
+    Factors, InputCache, Objective, PhaseTimer, Registration, Replicate, SummaryLayout,
+    TreatmentConfig, TreatmentOrder, debug_diff, derive_seed,
+};
 use colorize::AnsiColor;
-use criterion::Criterion;
+use criterion::{Bencher, BenchmarkId, Criterion, Throughput};
 use std::fmt::Debug;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
 
 /// An experiment to analyze the impact of algorithm factors, or parameter settings, on solution time
 /// over different data sets defined by input factors.
@@ -301,18 +313,584 @@ pub trait Experiment: Sized {
     /// [`bench`]: crate::Experiment::bench
     fn input(&mut self, input_variant: &Self::InputFactors) -> Self::Input;
 
+    /// Creates the `replicate`-th instance of the input for the given input factor levels.
+    ///
+    /// When the input is randomly generated, a single instance per input level might not be representative.
+    /// In such cases, [`num_replicates`] can be overwritten to create several instances of each input level,
+    /// and this method can be implemented to create a different instance for each replicate, such as by
+    /// using the `seed` of the replicate.
+    /// Each instance is then benchmarked with all algorithm variants, and the summary tables report the mean,
+    /// minimum, maximum and spread of the times across the replicates.
+    ///
+    /// Default implementation ignores the replicate and creates the input by the [`input`] method.
+    ///
+    /// [`num_replicates`]: crate::Experiment::num_replicates
+    /// [`input`]: crate::Experiment::input
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// fn input(&mut self, input_variant: &Self::InputFactors) -> Self::Input {
+    ///     let seed = self.input_seed(input_variant);
+    ///     self.input_replicate(input_variant, Replicate { index: 0, seed })
+    /// }
+    ///
+    /// fn input_replicate(&mut self, input_variant: &Self::InputFactors, replicate: Replicate) -> Self::Input {
+    ///     let mut rng = ChaCha8Rng::seed_from_u64(replicate.seed);
+    ///     new_graph(&mut rng, input_variant.num_nodes)
+    /// }
+    ///
+    /// fn num_replicates(&self) -> usize {
+    ///     5
+    /// }
+    /// ```
+    fn input_replicate(
+        &mut self,
+        input_variant: &Self::InputFactors,
+        replicate: Replicate,
+    ) -> Self::Input {
+        let _ = replicate;
+        self.input(input_variant)
+    }
+
+    /// Number of replicates, or instances, of each input level created by [`input_replicate`].
+    ///
+    /// Default implementation returns 1, in which case each input level has a single instance.
+    ///
+    /// It must be at least one; otherwise, [`try_bench`] returns the [`Error::ZeroReplicates`] error, and
+    /// [`bench`] panics, before running any treatment.
+    ///
+    /// [`input_replicate`]: crate::Experiment::input_replicate
+    /// [`try_bench`]: crate::Experiment::try_bench
+    /// [`bench`]: crate::Experiment::bench
+    /// [`Error::ZeroReplicates`]: crate::Error::ZeroReplicates
+    fn num_replicates(&self) -> usize {
+        1
+    }
+
+    /// Master seed of the experiment from which the seeds of all inputs are derived by [`input_seed`].
+    ///
+    /// The master seed is recorded in the `master_seed` column of the summary csv files, the json summary and
+    /// the markdown report so that the inputs of a run can be reproduced.
+    ///
+    /// Default implementation returns 0.
+    ///
+    /// [`input_seed`]: crate::Experiment::input_seed
+    fn master_seed(&self) -> u64 {
+        0
+    }
+
+    /// Seed of the input defined by the `input_variant`, which is derived from the [`master_seed`] of the
+    /// experiment and the [`key_long`] of the input by [`derive_seed`].
+    ///
+    /// It can be used in [`input`] to generate random inputs reproducibly, while each input gets a different
+    /// seed. The `seed` of the first [`Replicate`] of an input is equal to this seed.
+    ///
+    /// [`master_seed`]: crate::Experiment::master_seed
+    /// [`key_long`]: crate::Factors::key_long
+    /// [`derive_seed`]: crate::derive_seed
+    /// [`input`]: crate::Experiment::input
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// fn input(&mut self, input_variant: &Self::InputFactors) -> Self::Input {
+    ///     let mut rng = ChaCha8Rng::seed_from_u64(self.input_seed(input_variant));
+    ///     new_graph(&mut rng, input_variant.num_nodes)
+    /// }
+    /// ```
+    fn input_seed(&self, input_variant: &Self::InputFactors) -> u64 {
+        derive_seed(self.master_seed(), &input_variant.key_long())
+    }
+
+    /// Enables the on-disk cache of the inputs when returns `Some` [`InputCache`].
+    ///
+    /// When the cache is enabled, [`bench`] stores each instance of the inputs once it is created, and loads
+    /// it from the cache in the subsequent runs instead of calling [`input_replicate`], or [`input`], again.
+    /// This is useful when creating the inputs, such as large random graphs, takes a long time.
+    /// The cache requires the input to implement [`CachedInput`] defining how it is serialized.
+    ///
+    /// Default implementation returns `None`, in which case the inputs are created in every run.
+    ///
+    /// [`bench`]: crate::Experiment::bench
+    /// [`input_replicate`]: crate::Experiment::input_replicate
+    /// [`input`]: crate::Experiment::input
+    /// [`InputCache`]: crate::InputCache
+    /// [`CachedInput`]: crate::CachedInput
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// fn input_cache(&self) -> Option<InputCache<Self::Input>> {
+    ///     // the version must be changed whenever `input` is changed
+    ///     Some(InputCache::new("1"))
+    /// }
+    /// ```
+    fn input_cache(&self) -> Option<InputCache<Self::Input>> {
+        None
+    }
+
+    /// Returns the size of the `input` in bytes, such as the memory footprint of a graph.
+    ///
+    /// When the size is returned, it is logged when the input is created and added to the summary tables
+    /// as the "Input Size (bytes)" column next to the "Input Time (ms)" column which reports the time to
+    /// create each input.
+    ///
+    /// Default implementation returns `None`, in which case the size is not reported.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// fn input_size_bytes(&self, input: &Self::Input) -> Option<usize> {
+    ///     Some(input.len() * size_of::<u64>())
+    /// }
+    /// ```
+    fn input_size_bytes(&self, _: &Self::Input) -> Option<usize> {
+        None
+    }
+
     /// Executes the algorithm or task defined by the given `alg_variant` on the `input`, and returns the
     /// output.
     ///
     /// This is the method that is being analyzed in this experiment.
     fn execute(&mut self, alg_variant: &Self::AlgFactors, input: &Self::Input) -> Self::Output;
 
+    /// Executes the algorithm or task defined by the given `alg_variant` on the owned `input`, and returns the
+    /// output.
+    ///
+    /// This method is used instead of [`execute`] when [`execution_mode`] is [`ExecutionMode::Consumed`].
+    /// It allows to analyze algorithms that consume or mutate their input, such as in-place sorting, without
+    /// including the time to clone the input in the analysis.
+    ///
+    /// Default implementation calls [`execute`] with a reference to the `input`.
+    ///
+    /// [`execute`]: crate::Experiment::execute
+    /// [`execution_mode`]: crate::Experiment::execution_mode
+    /// [`ExecutionMode::Consumed`]: crate::ExecutionMode::Consumed
+    fn execute_mut(&mut self, alg_variant: &Self::AlgFactors, input: Self::Input) -> Self::Output {
+        self.execute(alg_variant, &input)
+    }
+
+    /// Executes the algorithm or task defined by the given `alg_variant` on the `input`, reporting time spent
+    /// in its named phases to the `timer`, and returns the output.
+    ///
+    /// This method is used instead of [`execute`] when [`phases`] returns a non-empty list.
+    /// Each phase is started by calling [`PhaseTimer::start`] with its name, and lasts until the next phase
+    /// starts or the execution completes.
+    /// Then, the summary additionally shows the mean time spent in each phase, while the analysis is still
+    /// based on the total time of the execution, measured by criterion's `iter_custom`.
+    ///
+    /// The total time is measured by an instance created by [`ExperimentMeasurement::create`], since criterion
+    /// does not expose the measurement of its runner.
+    /// Further, the timer reads the clock at each phase transition within the measured executions.
+    /// Therefore, the total time includes this overhead, in the order of tens of nanoseconds per phase, which
+    /// might be significant for very short executions; such treatments are better measured without phases.
+    ///
+    /// Default implementation calls [`execute`] without reporting any phases.
+    /// When overwritten, [`execute`] is typically implemented by calling this method with the default timer,
+    /// which is disabled; hence, it neither measures the phases nor allocates while validating the output or
+    /// counting the allocations of [`execute`].
+    ///
+    /// Note that phases are not reported when [`execution_mode`] is [`ExecutionMode::Consumed`].
+    ///
+    /// [`execute`]: crate::Experiment::execute
+    /// [`phases`]: crate::Experiment::phases
+    /// [`execution_mode`]: crate::Experiment::execution_mode
+    /// [`PhaseTimer::start`]: crate::PhaseTimer::start
+    /// [`ExecutionMode::Consumed`]: crate::ExecutionMode::Consumed
+    /// [`ExperimentMeasurement::create`]: crate::ExperimentMeasurement::create
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// fn execute(&mut self, alg_variant: &Self::AlgFactors, input: &Self::Input) -> Self::Output {
+    ///     // the default timer is disabled and does not affect the time or allocations of `execute`
+    ///     self.execute_phased(alg_variant, input, &mut PhaseTimer::default())
+    /// }
+    ///
+    /// fn execute_phased(
+    ///     &mut self,
+    ///     alg_variant: &Self::AlgFactors,
+    ///     input: &Self::Input,
+    ///     timer: &mut PhaseTimer,
+    /// ) -> Self::Output {
+    ///     timer.start("build");
+    ///     let store = build_store(alg_variant, input);
+    ///     timer.start("query");
+    ///     query(&store, input)
+    /// }
+    ///
+    /// fn phases(&self) -> Vec<&'static str> {
+    ///     vec!["build", "query"]
+    /// }
+    /// ```
+    fn execute_phased(
+        &mut self,
+        alg_variant: &Self::AlgFactors,
+        input: &Self::Input,
+        timer: &mut PhaseTimer,
+    ) -> Self::Output {
+        let _ = timer;
+        self.execute(alg_variant, input)
+    }
+
+    /// Names of the phases reported by [`execute_phased`], in the order they will be presented in the summary.
+    ///
+    /// Default implementation returns an empty vector, in which case [`execute`] is measured and no phase is
+    /// reported.
+    ///
+    /// [`execute`]: crate::Experiment::execute
+    /// [`execute_phased`]: crate::Experiment::execute_phased
+    fn phases(&self) -> Vec<&'static str> {
+        vec![]
+    }
+
+    /// Determines how the algorithm variants access the input while being measured.
+    ///
+    /// Default implementation returns [`ExecutionMode::Borrowed`], in which case [`execute`] is measured.
+    ///
+    /// When [`ExecutionMode::Consumed`] is returned, [`execute_mut`] is measured with an owned copy of the input.
+    /// Copies are created by the `setup` function of the mode, and the time to create them is excluded from the
+    /// measurement.
+    ///
+    /// [`execute`]: crate::Experiment::execute
+    /// [`execute_mut`]: crate::Experiment::execute_mut
+    /// [`ExecutionMode::Borrowed`]: crate::ExecutionMode::Borrowed
+    /// [`ExecutionMode::Consumed`]: crate::ExecutionMode::Consumed
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// fn execute(&mut self, alg_variant: &Self::AlgFactors, input: &Self::Input) -> Self::Output {
+    ///     self.execute_mut(alg_variant, input.clone())
+    /// }
+    ///
+    /// fn execute_mut(&mut self, alg_variant: &Self::AlgFactors, mut input: Self::Input) -> Self::Output {
+    ///     input.sort();
+    ///     input
+    /// }
+    ///
+    /// fn execution_mode(&self) -> ExecutionMode<Self::Input> {
+    ///     ExecutionMode::Consumed {
+    ///         setup: Clone::clone,
+    ///         batch_size: BatchSize::LargeInput,
+    ///     }
+    /// }
+    /// ```
+    fn execution_mode(&self) -> ExecutionMode<Self::Input> {
+        ExecutionMode::Borrowed
+    }
+
+    /// Returns the amount of work processed by each execution on the input created for the given input factor
+    /// levels, such as the number of elements or bytes of the input.
+    ///
+    /// Default implementation returns `None`, in which case only the time is reported.
+    ///
+    /// When `Some` throughput is returned, it is passed to the criterion benchmark group of the input, and the summary
+    /// tables additionally show the throughput of each treatment, such as "Elements/s" or "Bytes/s", which makes the
+    /// results comparable across inputs of different sizes.
+    ///
+    /// Since criterion's benchmark group keeps the last throughput that is set, `Some` throughput must be returned
+    /// either for all or none of the inputs; otherwise, [`try_bench`] returns [`Error::InconsistentThroughput`]
+    /// before running any of the treatments.
+    ///
+    /// [`try_bench`]: crate::Experiment::try_bench
+    /// [`Error::InconsistentThroughput`]: crate::Error::InconsistentThroughput
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// fn throughput(&self, input_variant: &Settings) -> Option<Throughput> {
+    ///     Some(Throughput::Elements(input_variant.len as u64))
+    /// }
+    /// ```
+    fn throughput(&self, _: &Self::InputFactors) -> Option<Throughput> {
+        None
+    }
+
+    /// Returns the criterion configuration of the treatment defined by the given input and algorithm factor levels,
+    /// such as its sample size, warm-up and measurement times, sampling mode, noise threshold and the parameters
+    /// of the statistical analysis.
+    ///
+    /// Default implementation returns the default [`TreatmentConfig`] which does not change any of the settings;
+    /// hence, all treatments use the configuration of the criterion benchmark runner.
+    ///
+    /// It can be overwritten when treatments require different settings; for instance, executions on large inputs
+    /// might require longer measurement times and flat sampling, while the defaults are sufficient for small inputs.
+    /// The configuration is applied to the benchmark group right before the treatment is benchmarked.
+    ///
+    /// Note that criterion cannot reset a setting of a benchmark group once it is set; hence, a setting returned
+    /// for a treatment is inherited by the subsequent treatments of the same benchmark group, which are the
+    /// treatments of the same input unless the registration is flat, unless they set it again.
+    /// Returning the same fields for all treatments avoids this inheritance.
+    ///
+    /// Configurations are validated before any treatment is run; [`try_bench`] returns the
+    /// [`Error::InvalidTreatmentConfig`] error, and [`bench`] panics, if criterion would reject any of them,
+    /// such as a sample size less than 10 or a zero measurement time.
+    ///
+    /// [`TreatmentConfig`]: crate::TreatmentConfig
+    /// [`try_bench`]: crate::Experiment::try_bench
+    /// [`bench`]: crate::Experiment::bench
+    /// [`Error::InvalidTreatmentConfig`]: crate::Error::InvalidTreatmentConfig
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// fn configure(&self, input_variant: &Settings, _: &Params) -> TreatmentConfig {
+    ///     match input_variant.len {
+    ///         len if len >= 1 << 20 => TreatmentConfig {
+    ///             measurement_time: Some(Duration::from_secs(30)),
+    ///             sampling_mode: Some(SamplingMode::Flat),
+    ///             ..Default::default()
+    ///         },
+    ///         _ => TreatmentConfig {
+    ///             measurement_time: Some(Duration::from_secs(5)),
+    ///             sampling_mode: Some(SamplingMode::Auto),
+    ///             ..Default::default()
+    ///         },
+    ///     }
+    /// }
+    /// ```
+    fn configure(&self, _: &Self::InputFactors, _: &Self::AlgFactors) -> TreatmentConfig {
+        TreatmentConfig::default()
+    }
+
+    /// Returns the total time budget of the experiment.
+    ///
+    /// Default implementation returns `None`, in which case all treatments are executed.
+    ///
+    /// When `Some` budget is returned, no new treatment is started once the time elapsed since the beginning of the
+    /// experiment exceeds the budget. The treatments which are not executed are marked as `SKIPPED` in the summary
+    /// tables. Note that the treatment which is running when the budget is exhausted is completed; hence, the
+    /// experiment might take longer than the budget.
+    ///
+    /// Time limits of individual treatments can be defined by the `time_limit` field of the [`TreatmentConfig`]
+    /// returned by [`configure`].
+    ///
+    /// [`TreatmentConfig`]: crate::TreatmentConfig
+    /// [`configure`]: crate::Experiment::configure
+    fn time_budget(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Returns the order in which the treatments are executed.
+    ///
+    /// Default implementation returns [`TreatmentOrder::Canonical`], where inputs are executed in the outer loop
+    /// and algorithm variants in the inner loop, in the order they are provided.
+    ///
+    /// Since a drift in the environment during a long experiment, such as thermal throttling, systematically
+    /// penalizes the later treatments in the canonical order, it can be overwritten to execute the treatments
+    /// in a seeded random order by [`TreatmentOrder::Randomized`] or [`TreatmentOrder::BlockedRandomized`].
+    /// In these cases, the execution order is written to the "order_{name}.csv" file together with the seed,
+    /// which is also recorded in the json summary and the markdown report.
+    /// The summary tables always present the treatments in the canonical order.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// fn treatment_order(&self) -> TreatmentOrder {
+    ///     TreatmentOrder::BlockedRandomized { seed: 42 }
+    /// }
+    /// ```
+    fn treatment_order(&self) -> TreatmentOrder {
+        TreatmentOrder::Canonical
+    }
+
+    /// Returns how the treatments are registered as criterion benchmarks.
+    ///
+    /// Default implementation returns [`Registration::Flat`], where all treatments are registered in a single
+    /// benchmark group with their short keys as the benchmark ids.
+    ///
+    /// It can be overwritten to return [`Registration::Parameterized`] with the name of a numeric input factor,
+    /// such as the length of the input. Then, each treatment is registered as
+    /// `BenchmarkId::new(variant_key, input_param)` where the parameter is the level of this factor.
+    /// This allows criterion's html report to draw line charts comparing the algorithm variants across the
+    /// levels of the factor, one chart for each combination of the levels of the remaining input factors.
+    ///
+    /// Since each such combination is a separate benchmark group, the treatments are executed group by group,
+    /// and the settings of the [`TreatmentConfig`] which are not set again are reset to the criterion defaults
+    /// at the beginning of each group.
+    /// The summary tables are not affected by the registration.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// fn registration(&self) -> Registration {
+    ///     Registration::Parameterized { input_factor: "len" }
+    /// }
+    /// ```
+    ///
+    /// [`TreatmentConfig`]: crate::TreatmentConfig
+    fn registration(&self) -> Registration {
+        Registration::Flat
+    }
+
+    /// Returns the format of the csv files created by the experiment, such as the summary csv.
+    ///
+    /// Default implementation returns the default [`CsvFormat`], which uses a comma as the delimiter and writes
+    /// the summary only in the wide format, where each row is a treatment.
+    /// Fields are always quoted as defined by RFC 4180 when necessary; hence, factor levels containing the
+    /// delimiter or double quotes do not corrupt the files.
+    /// The delimiter must not be a double quote or a line break, which is checked before running the experiment.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// fn csv_format(&self) -> CsvFormat {
+    ///     CsvFormat {
+    ///         delimiter: ';',
+    ///         long_format: true,
+    ///     }
+    /// }
+    /// ```
+    fn csv_format(&self) -> CsvFormat {
+        CsvFormat::default()
+    }
+
+    /// Returns the layout of the summary table printed to the console at the end of the experiment.
+    ///
+    /// Default implementation returns [`SummaryLayout::Table`], where each row is a treatment.
+    ///
+    /// When there are many inputs and algorithm variants, [`SummaryLayout::Pivot`] prints a compact table
+    /// where each row is an input and each column is an algorithm variant, with a final row aggregating each
+    /// variant across all inputs; while [`SummaryLayout::Both`] prints both tables.
+    /// Regardless of the layout, the pivot table is written to the "pivot_{name}.csv" file.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// fn summary_layout(&self) -> SummaryLayout {
+    ///     SummaryLayout::Pivot
+    /// }
+    /// ```
+    fn summary_layout(&self) -> SummaryLayout {
+        SummaryLayout::Table
+    }
+
+    /// Returns a description of the experiment, such as its purpose and the hypotheses to be tested.
+    ///
+    /// Default implementation returns `None`.
+    ///
+    /// The description is the introduction of the "report_{name}.md" file which presents the factors, the
+    /// summary table, the best and worst variants per input and the recommended variant in markdown.
+    /// When it is `None`, a brief description is generated from the numbers of inputs and variants.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// fn description(&self) -> Option<String> {
+    ///     Some("Compares sequential and parallel search on arrays of different lengths.".to_string())
+    /// }
+    /// ```
+    fn description(&self) -> Option<String> {
+        None
+    }
+
+    /// Lifecycle hook called by [`bench`] once before any of the treatments is executed.
+    ///
+    /// It can be used to set up resources shared by all treatments, such as warming up a thread pool.
+    ///
+    /// All lifecycle hooks do nothing and return `None` by default.
+    /// A hook might return a message, such as the state of a resource, which is then logged by [`bench`].
+    /// Note that the time spent in the hooks is not included in the measurements.
+    ///
+    /// Lifecycle hooks of the experiment are called in the following order, where the loops follow the
+    /// [`treatment_order`]:
+    ///
+    /// * `before_experiment`
+    ///   * `before_input`, once before each instance of an input is created
+    ///     * `before_treatment`, before each treatment on this input is executed
+    ///     * `after_treatment`, after each treatment on this input is executed
+    ///   * `after_input`, once after the instance of the input is dropped
+    /// * `after_experiment`
+    ///
+    /// Hooks of the inputs and treatments are not called for the treatments skipped due to the time budget.
+    /// While creating the [`plan`], only the hooks of the inputs are called around creating each instance, and
+    /// only if [`plan_input_times`] is true.
+    ///
+    /// [`bench`]: crate::Experiment::bench
+    /// [`treatment_order`]: crate::Experiment::treatment_order
+    /// [`plan`]: crate::Experiment::plan
+    /// [`plan_input_times`]: crate::Experiment::plan_input_times
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// fn before_experiment(&mut self, name: &str) -> Option<String> {
+    ///     let num_threads = self.pool.warm_up();
+    ///     Some(format!("{name}: warmed up {num_threads} threads"))
+    /// }
+    /// ```
+    fn before_experiment(&mut self, _name: &str) -> Option<String> {
+        None
+    }
+
+    /// Lifecycle hook called by [`bench`] before an instance of the input defined by the `input_variant` is
+    /// created; see [`before_experiment`] for the order of the hooks.
+    ///
+    /// [`bench`]: crate::Experiment::bench
+    /// [`before_experiment`]: crate::Experiment::before_experiment
+    fn before_input(&mut self, _input_variant: &Self::InputFactors) -> Option<String> {
+        None
+    }
+
+    /// Lifecycle hook called by [`bench`] before the treatment of the algorithm defined by the `alg_variant`
+    /// on the input defined by the `input_variant` is executed; see [`before_experiment`] for the order of
+    /// the hooks.
+    ///
+    /// It can be used to reset the state accumulated in the experiment by the previous treatments.
+    ///
+    /// [`bench`]: crate::Experiment::bench
+    /// [`before_experiment`]: crate::Experiment::before_experiment
+    fn before_treatment(
+        &mut self,
+        _input_variant: &Self::InputFactors,
+        _alg_variant: &Self::AlgFactors,
+    ) -> Option<String> {
+        None
+    }
+
+    /// Lifecycle hook called by [`bench`] after the treatment of the algorithm defined by the `alg_variant`
+    /// on the input defined by the `input_variant` is executed; see [`before_experiment`] for the order of
+    /// the hooks.
+    ///
+    /// [`bench`]: crate::Experiment::bench
+    /// [`before_experiment`]: crate::Experiment::before_experiment
+    fn after_treatment(
+        &mut self,
+        _input_variant: &Self::InputFactors,
+        _alg_variant: &Self::AlgFactors,
+    ) -> Option<String> {
+        None
+    }
+
+    /// Lifecycle hook called by [`bench`] after all treatments on an instance of the input defined by the
+    /// `input_variant` are executed and the instance is dropped; see [`before_experiment`] for the order of
+    /// the hooks.
+    ///
+    /// It can be used to release the resources acquired for the input.
+    ///
+    /// [`bench`]: crate::Experiment::bench
+    /// [`before_experiment`]: crate::Experiment::before_experiment
+    fn after_input(&mut self, _input_variant: &Self::InputFactors) -> Option<String> {
+        None
+    }
+
+    /// Lifecycle hook called by [`bench`] once after all treatments are executed and the experiment is
+    /// summarized; see [`before_experiment`] for the order of the hooks.
+    ///
+    /// [`bench`]: crate::Experiment::bench
+    /// [`before_experiment`]: crate::Experiment::before_experiment
+    fn after_experiment(&mut self, _name: &str) -> Option<String> {
+        None
+    }
+
     /// Returns the expected output that the `execute` must produce for the given input factor levels and input
     /// created for these factor levels.
     ///
     /// Default implementation returns `None`, in which case, this validation is skipped.
     /// When the method is overwritten and returns `Some(X)`, output of the `execute` method must also return `X`
-    /// for the given input.
+    /// for the given input, where the comparison is performed by [`outputs_match`].
     ///
     /// Note that this validation test assumes that `execute` is pure in the sense that it deterministically creates
     /// the same output every time it is called with the same input, regardless of the algorithm variant.
@@ -326,6 +904,7 @@ pub trait Experiment: Sized {
     /// time is not included in the analysis, and hence, it does not impact the analysis.
     ///
     /// [`validate_output`]: crate::Experiment::validate_output
+    /// [`outputs_match`]: crate::Experiment::outputs_match
     fn expected_output(&self, _: &Self::InputFactors, _: &Self::Input) -> Option<Self::Output> {
         None
     }
@@ -339,61 +918,454 @@ pub trait Experiment: Sized {
     /// time is not included in the analysis, and hence, it does not impact the analysis.
     fn validate_output(&self, _: &Self::InputFactors, _: &Self::Input, _: &Self::Output) {}
 
+    /// Computes secondary responses, or metrics, of the `output` created by any one of the algorithm variants
+    /// for the given input.
+    ///
+    /// Time is the primary response of the experiment.
+    /// However, for heuristics or approximation algorithms, we are also interested in the quality of the output,
+    /// such as the objective value of a solution.
+    /// Each returned `(name, value)` pair is added as an additional column named `name` to the summary tables.
+    ///
+    /// Default implementation returns an empty vector, in which case no metric is recorded.
+    ///
+    /// Similar to validation, metrics are computed only once per (input, algorithm) combination, and the time
+    /// spent for computing them is not included in the analysis.
+    ///
+    /// When metrics are recorded, the summary additionally marks the algorithm variants which are Pareto-optimal
+    /// over time and metrics for each input and across all inputs, and lists them in the "pareto_{name}.csv"
+    /// file. Direction of each metric is defined by [`metric_objective`].
+    /// A variant is Pareto-optimal across all inputs when no other variant is at least as good on every input
+    /// and better on some input, which is labeled as the "per_input" dominance in the file; the responses
+    /// listed for these variants are the means over the inputs, on which the front is not computed.
+    ///
+    /// [`metric_objective`]: crate::Experiment::metric_objective
+    fn metrics(
+        &self,
+        _: &Self::InputFactors,
+        _: &Self::Input,
+        _: &Self::Output,
+    ) -> Vec<(&'static str, f64)> {
+        vec![]
+    }
+
+    /// Objective of the metric with the given `metric` name returned by [`metrics`].
+    ///
+    /// Objectives are used to compute Pareto-optimal algorithm variants over time and metrics, where time is
+    /// always minimized.
+    ///
+    /// Default implementation returns [`Objective::Minimize`] for all metrics.
+    ///
+    /// [`metrics`]: crate::Experiment::metrics
+    /// [`Objective::Minimize`]: crate::Objective::Minimize
+    fn metric_objective(&self, _metric: &str) -> Objective {
+        Objective::Minimize
+    }
+
+    /// Compares the `expected` output returned by [`expected_output`] with the `actual` output created by
+    /// the `execute` method.
+    ///
+    /// Returns `Ok(())` if the outputs match; and an error message describing the mismatch otherwise.
+    ///
+    /// Default implementation requires the outputs to be exactly equal, and describes the mismatch by
+    /// [`debug_diff`] which reports the first difference together with a bounded context window.
+    ///
+    /// When the outputs do not match, [`bench`] additionally writes full debug representations of the expected
+    /// and actual outputs to files under the "target/criterion/{name}/mismatches" directory.
+    /// It can be overwritten to allow approximate comparisons, such as floating-point costs or objective
+    /// values of heuristics, for which [`Tolerance`] provides absolute and relative tolerance helpers.
+    ///
+    /// [`expected_output`]: crate::Experiment::expected_output
+    /// [`Tolerance`]: crate::Tolerance
+    /// [`debug_diff`]: crate::debug_diff
+    /// [`bench`]: crate::Experiment::bench
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// fn outputs_match(&self, expected: &f64, actual: &f64) -> Result<(), String> {
+    ///     Tolerance::relative(1e-6).check(*expected, *actual)
+    /// }
+    /// ```
+    fn outputs_match(&self, expected: &Self::Output, actual: &Self::Output) -> Result<(), String> {
+        match expected == actual {
+            true => Ok(()),
+            false => Err(debug_diff(expected, actual)),
+        }
+    }
+
+    /// Returns whether or not the [`plan`] creates the inputs in order to include their construction times in
+    /// the estimated duration of the experiment.
+    ///
+    /// Default implementation returns false, since creating large inputs might take as long as the experiment
+    /// itself; it can be overwritten to return true when the inputs are expensive to create relative to the
+    /// measurement of the treatments.
+    /// Then, each instance of the inputs is created once by the plan, between the calls to the
+    /// [`before_input`] and [`after_input`] hooks.
+    ///
+    /// [`plan`]: crate::Experiment::plan
+    /// [`before_input`]: crate::Experiment::before_input
+    /// [`after_input`]: crate::Experiment::after_input
+    fn plan_input_times(&self) -> bool {
+        false
+    }
+
+    /// Creates the plan of the experiment without running it.
+    ///
+    /// The plan lists every treatment for the combinations of `input_levels` and `alg_levels` together with
+    /// its long and short keys, and estimates the total duration of the experiment from the warm-up and
+    /// measurement times of the treatments, and the time to create the inputs if [`plan_input_times`] is true.
+    ///
+    /// The plan can also be printed by [`bench`] without measuring by setting the [`PLAN_ENV_VAR`] environment
+    /// variable; for instance, `ORX_CRITERION_PLAN=1 cargo bench --bench tuning_example`.
+    ///
+    /// [`bench`]: crate::Experiment::bench
+    /// [`PLAN_ENV_VAR`]: crate::PLAN_ENV_VAR
+    /// [`plan_input_times`]: crate::Experiment::plan_input_times
+    fn plan(
+        &mut self,
+        name: &str,
+        input_levels: &[Self::InputFactors],
+        alg_levels: &[Self::AlgFactors],
+    ) -> ExperimentPlan {
+        ExperimentPlan::new(self, name, input_levels, alg_levels)
+    }
+
     /// Executes the experiment using criterion (`c`) benchmarks.
     ///
     /// Each combination of `input_levels` and `alg_levels` will be benchmarked.
     ///
     /// At the end of the criterion benchmark run, summary tables will be created to enable factorial analysis.
-    fn bench(
+    ///
+    /// When the [`PLAN_ENV_VAR`] environment variable is set, the [`plan`] of the experiment is printed and the
+    /// method returns without measuring.
+    ///
+    /// [`PLAN_ENV_VAR`]: crate::PLAN_ENV_VAR
+    /// [`plan`]: crate::Experiment::plan
+    ///
+    /// The benchmarks use the measurement `M` of criterion, which is the wall time by default.
+    /// Custom measurements can be used by configuring criterion with [`with_measurement`], provided that the
+    /// measurement implements [`ExperimentMeasurement`].
+    /// The unit of the measured values in the summary tables is then determined by the measurement's formatter.
+    ///
+    /// [`with_measurement`]: criterion::Criterion::with_measurement
+    ///
+    /// Finally, the results are returned as an [`ExperimentResult`] which allows to post-process them
+    /// programmatically, such as obtaining the best algorithm variant for each input or across all inputs.
+    /// In the planning mode, none of the treatments is executed; the result does not contain any estimates and
+    /// all treatments have the [`TreatmentStatus::NotRun`] status.
+    ///
+    /// [`TreatmentStatus::NotRun`]: crate::TreatmentStatus::NotRun
+    ///
+    /// [`ExperimentResult`]: crate::ExperimentResult
+    ///
+    /// # Panics
+    ///
+    /// Panics if the output of a treatment does not match its [`expected_output`], if the [`throughput`] is
+    /// defined for only some of the inputs, if the delimiter of the [`csv_format`] is a double quote or a line
+    /// break, if [`num_replicates`] is zero, if the [`configure`]d settings of a treatment are invalid, or if
+    /// the results of the experiment cannot be reported, such as
+    /// when the summary files cannot be written.
+    /// See [`try_bench`] to handle these failures.
+    ///
+    /// [`throughput`]: crate::Experiment::throughput
+    /// [`csv_format`]: crate::Experiment::csv_format
+    /// [`num_replicates`]: crate::Experiment::num_replicates
+    /// [`configure`]: crate::Experiment::configure
+    ///
+    /// [`expected_output`]: crate::Experiment::expected_output
+    /// [`try_bench`]: crate::Experiment::try_bench
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let result = SearchExp.bench(c, "tuning_example", &input_levels, &alg_levels);
+    /// let best = result.best_overall();
+    /// ```
+    fn bench<'a, M: ExperimentMeasurement>(
         &mut self,
-        c: &mut Criterion,
+        c: &mut Criterion<M>,
         name: &str,
-        input_levels: &[Self::InputFactors],
-        alg_levels: &[Self::AlgFactors],
-    ) {
+        input_levels: &'a [Self::InputFactors],
+        alg_levels: &'a [Self::AlgFactors],
+    ) -> ExperimentResult<'a, Self> {
+        self.try_bench(c, name, input_levels, alg_levels)
+            .expect("Failed to run the experiment")
+    }
+
+    /// Executes the experiment using criterion (`c`) benchmarks exactly as [`bench`]; however, returns an
+    /// [`Error`] rather than panicking when the results of the experiment cannot be reported, such as when
+    /// the summary files cannot be written or the summary table cannot be printed, or when the [`throughput`]
+    /// is defined for only some of the inputs, the delimiter of the [`csv_format`] is invalid,
+    /// [`num_replicates`] is zero, the [`configure`]d settings of a treatment are invalid, or the input factor of
+    /// the [`Registration::Parameterized`] registration is not one of the input [`factor_names`].
+    /// These are validated before any treatment is run.
+    ///
+    /// Note that the output of a treatment not matching its [`expected_output`] is still an assertion failure.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the output of a treatment does not match its [`expected_output`].
+    ///
+    /// [`bench`]: crate::Experiment::bench
+    /// [`throughput`]: crate::Experiment::throughput
+    /// [`csv_format`]: crate::Experiment::csv_format
+    /// [`num_replicates`]: crate::Experiment::num_replicates
+    /// [`configure`]: crate::Experiment::configure
+    /// [`factor_names`]: crate::Factors::factor_names
+    /// [`Error`]: crate::Error
+    /// [`expected_output`]: crate::Experiment::expected_output
+    fn try_bench<'a, M: ExperimentMeasurement>(
+        &mut self,
+        c: &mut Criterion<M>,
+        name: &str,
+        input_levels: &'a [Self::InputFactors],
+        alg_levels: &'a [Self::AlgFactors],
+    ) -> Result<ExperimentResult<'a, Self>, Error> {
         let num_i = input_levels.len();
         let num_a = alg_levels.len();
         let num_t = input_levels.len() * alg_levels.len();
+        let num_r = self.num_replicates();
+        let num_runs = num_t * num_r;
 
-        let log = format!(
-            "\n\n\n# {name} benchmarks with {num_i} data points and {num_a} variants => {num_t} treatments"
-        );
+        let log = match num_r {
+            1 => format!(
+                "\n\n\n# {name} benchmarks with {num_i} data points and {num_a} variants => {num_t} treatments"
+            ),
+            _ => format!(
+                "\n\n\n# {name} benchmarks with {num_i} data points and {num_a} variants => {num_t} treatments, each on {num_r} replicates => {num_runs} runs"
+            ),
+        };
         println!("{}", log.bold().underlined());
 
-        let mut group = c.benchmark_group(name);
-        for (i, input_variant) in input_levels.iter().enumerate() {
-            let datum_str = input_variant.key_long();
-            let i = i + 1;
-            let log = format!("\n\n\n\n\n## Data point [{i}/{num_i}]: {datum_str}");
-            println!("{}", log.yellow().bold());
-
-            let input = self.input(input_variant);
-            for (a, alg_variant) in alg_levels.iter().enumerate() {
-                let a = a + 1;
-                let idx = (i - 1) * num_a + a;
-                let run_str = self.run_key_long(input_variant, alg_variant);
-                let log = format!("\n### [{idx}/{num_t} || {a}/{num_a}]: {run_str}");
+        if num_r == 0 {
+            return Err(Error::ZeroReplicates);
+        }
+        let throughputs = input_throughputs(self, input_levels)?;
+        self.csv_format().validate()?;
+        self.validate_registration()?;
+        for input_variant in input_levels {
+            for alg_variant in alg_levels {
+                let config = self.configure(input_variant, alg_variant);
+                config
+                    .validate()
+                    .map_err(|reason| Error::InvalidTreatmentConfig {
+                        treatment: self.run_key_long(input_variant, alg_variant),
+                        reason,
+                    })?;
+            }
+        }
+
+        if is_plan_mode() {
+            self.plan(name, input_levels, alg_levels).try_print()?;
+            let estimates = vec![vec![None; num_a]; num_i];
+            let records = vec![vec![TreatmentRecord::not_run(); num_a]; num_i];
+            return Ok(ExperimentResult::new(
+                name,
+                M::unit(),
+                input_levels,
+                alg_levels,
+                &estimates,
+                &records,
+            ));
+        }
+
+        log_hook("before_experiment", self.before_experiment(name));
+
+        let begin = Instant::now();
+        let budget = self.time_budget();
+        let is_exhausted = || budget.is_some_and(|x| begin.elapsed() >= x);
+
+        let treatment_order = self.treatment_order();
+        let order = self.execution_order(name, input_levels, alg_levels);
+        if treatment_order != TreatmentOrder::Canonical || self.registration() != Registration::Flat
+        {
+            create_order_csv(
+                self,
+                name,
+                input_levels,
+                alg_levels,
+                treatment_order,
+                &order,
+            )
+            .map_err(Error::write(self.order_csv_path(name)))?;
+            let log = format!(
+                "\nTreatments are executed in {treatment_order:?} order which is written to:\n{:?}\n",
+                self.order_csv_path(name)
+            );
+            println!("{}", log.italic());
+        }
+
+        // instances of the inputs, where instance k is the replicate (k % num_r) of the input (k / num_r)
+        let mut records = vec![vec![TreatmentRecord::default(); num_a]; num_i * num_r];
+        let mut inputs: Vec<Option<Self::Input>> = (0..num_i * num_r).map(|_| None).collect();
+        let mut input_records = vec![None; num_i * num_r];
+        let mut num_remaining = vec![num_a; num_i * num_r];
+        let group_ids: Vec<_> = (0..num_i * num_r)
+            .map(|k| self.benchmark_group_id(name, &input_levels[k / num_r], k % num_r))
+            .collect();
+        let mut idx = 0;
+        for group_order in order.chunk_by(|x, y| group_ids[x.0] == group_ids[y.0]) {
+            let mut group = c.benchmark_group(&group_ids[group_order[0].0]);
+            for &(k, a) in group_order {
+                idx += 1;
+                // release the inputs all treatments of which are completed
+                release_inputs(self, input_levels, &mut inputs, &num_remaining);
+                num_remaining[k] -= 1;
+
+                let (i, r) = (k / num_r, k % num_r);
+                let input_variant = &input_levels[i];
+                let alg_variant = &alg_levels[a];
+
+                if inputs[k].is_none() && !is_exhausted() {
+                    let datum_str = input_variant.key_long();
+                    let log = match num_r {
+                        1 => format!("\n\n\n\n\n## Data point [{}/{num_i}]: {datum_str}", i + 1),
+                        _ => format!(
+                            "\n\n\n\n\n## Data point [{}/{num_i}], replicate [{}/{num_r}]: {datum_str}",
+                            i + 1,
+                            r + 1
+                        ),
+                    };
+                    println!("{}", log.yellow().bold());
+                    log_hook("before_input", self.before_input(input_variant));
+                    let input_begin = Instant::now();
+                    let input = create_input(self, name, input_variant, r);
+                    let elapsed = input_begin.elapsed();
+                    let size_bytes = self.input_size_bytes(&input);
+                    let log = match size_bytes {
+                        Some(size) => {
+                            format!("Input is prepared in {elapsed:?} with size {size} bytes")
+                        }
+                        None => format!("Input is prepared in {elapsed:?}"),
+                    };
+                    println!("{}", log.italic());
+                    input_records[k] = Some(InputRecord {
+                        elapsed,
+                        size_bytes,
+                    });
+                    inputs[k] = Some(input);
+                }
+
+                let run_str = self.instance_key_long(input_variant, r, alg_variant);
+                let log = format!("\n### [{idx}/{num_runs} || {}/{num_a}]: {run_str}", a + 1);
                 println!("{}", log.green());
 
-                let execution_name = self.run_key_short(input_variant, alg_variant);
+                let input = match &inputs[k] {
+                    Some(input) if !is_exhausted() => input,
+                    _ => {
+                        let log = "SKIPPED: time budget of the experiment is exhausted";
+                        println!("{}", log.red());
+                        records[k][a] = TreatmentRecord::skipped();
+                        continue;
+                    }
+                };
+
+                log_hook(
+                    "before_treatment",
+                    self.before_treatment(input_variant, alg_variant),
+                );
+
+                if let Some(throughput) = &throughputs[i] {
+                    group.throughput(throughput.clone());
+                }
+
+                let execution_name = self.instance_key_short(input_variant, r, alg_variant);
 
-                group.bench_with_input(&execution_name, &input, |b, input| {
-                    let output = self.execute(alg_variant, input);
-                    self.validate_output(input_variant, input, &output);
-                    if let Some(expected_output) = self.expected_output(input_variant, input) {
-                        assert_eq!(
-                            output, expected_output,
-                            "Output of run is not equal to expected output. Run: {run_str}",
+                let config = self.configure(input_variant, alg_variant);
+                config.apply(&mut group);
+
+                let mut record = None;
+                let mut is_run = false;
+                if let Some(time_limit) = config.time_limit {
+                    let mut pilot =
+                        record_treatment(self, name, input_variant, r, alg_variant, input);
+                    if pilot.elapsed > time_limit {
+                        let log = format!(
+                            "TIMEOUT: pilot execution took {:?} exceeding the time limit {:?}; measurement is skipped",
+                            pilot.elapsed, time_limit
+                        );
+                        println!("{}", log.red());
+                        pilot.status = TreatmentStatus::TimedOut;
+                        records[k][a] = pilot;
+                        log_hook(
+                            "after_treatment",
+                            self.after_treatment(input_variant, alg_variant),
                         );
+                        continue;
+                    }
+                    record = Some(pilot);
+                }
+
+                let mut timer = PhaseTimer::new(self.phases());
+                let is_phased = !timer.durations().is_empty();
+                let parameter = self.benchmark_parameter(input_variant);
+                let mut routine = |b: &mut Bencher<'_, M>, input: &Self::Input| {
+                    is_run = true;
+                    if record.is_none() {
+                        record = Some(record_treatment(
+                            self,
+                            name,
+                            input_variant,
+                            r,
+                            alg_variant,
+                            input,
+                        ));
                     }
 
-                    b.iter(|| self.execute(alg_variant, input));
-                });
+                    match self.execution_mode() {
+                        ExecutionMode::Borrowed if is_phased => b.iter_custom(|iters| {
+                            // criterion does not expose the measurement of the runner
+                            let measurement = M::create();
+                            let begin = measurement.start();
+                            for _ in 0..iters {
+                                black_box(self.execute_phased(alg_variant, input, &mut timer));
+                                timer.end_execution();
+                            }
+                            measurement.end(begin)
+                        }),
+                        ExecutionMode::Borrowed => b.iter(|| self.execute(alg_variant, input)),
+                        ExecutionMode::Consumed { setup, batch_size } => b.iter_batched(
+                            || setup(input),
+                            |input| self.execute_mut(alg_variant, input),
+                            batch_size,
+                        ),
+                    }
+                };
+                match parameter {
+                    Some(parameter) => group.bench_with_input(
+                        BenchmarkId::new(alg_variant.key_short(), parameter),
+                        input,
+                        &mut routine,
+                    ),
+                    None => group.bench_with_input(&execution_name, input, &mut routine),
+                };
+                // criterion does not call the routine of the benchmarks excluded by its filter
+                let mut record = match is_run {
+                    true => record.unwrap_or_default(),
+                    false => {
+                        let log = "NOT RUN: the treatment is not run by criterion, such as when it is excluded by the benchmark filter";
+                        println!("{}", log.red());
+                        TreatmentRecord::not_run()
+                    }
+                };
+                record.phases = timer.mean_durations();
+                records[k][a] = record;
+                log_hook(
+                    "after_treatment",
+                    self.after_treatment(input_variant, alg_variant),
+                );
             }
+            group.finish();
         }
+        release_inputs(self, input_levels, &mut inputs, &num_remaining);
+        for (instance_records, input) in records.iter_mut().zip(input_records) {
+            instance_records.iter_mut().for_each(|x| x.input = input);
+        }
+
+        let result = try_summarize::<Self, M>(self, name, input_levels, alg_levels, &records)?;
 
-        group.finish();
+        log_hook("after_experiment", self.after_experiment(name));
 
-        summarize(self, name, input_levels, alg_levels);
+        Ok(result)
     }
 }
@@ -1,4 +1,6 @@
-use crate::{Experiment, Factors};
+use crate::factors::join;
+use crate::registration::{filename_safe, replace_special_chars};
+use crate::{Error, Experiment, Factors, Registration};
 use std::path::PathBuf;
 
 pub trait ExperimentSealed: Experiment {
@@ -22,28 +24,263 @@ pub trait ExperimentSealed: Experiment {
         format!("{}/{}", input_variant.key_short(), alg_variant.key_short())
     }
 
+    /// Long key of the `replicate`-th instance of the input defined by the `input_variant`.
+    ///
+    /// It is equal to the key of the input when there exists a single replicate of each input.
+    fn input_instance_key_long(
+        &self,
+        input_variant: &Self::InputFactors,
+        replicate: usize,
+    ) -> String {
+        match self.num_replicates() {
+            1 => input_variant.key_long(),
+            _ => format!("{}/replicate:{}", input_variant.key_long(), replicate + 1),
+        }
+    }
+
+    /// Short key of the `replicate`-th instance of the input defined by the `input_variant`.
+    ///
+    /// It is equal to the short key of the input when there exists a single replicate of each input.
+    fn input_instance_key_short(
+        &self,
+        input_variant: &Self::InputFactors,
+        replicate: usize,
+    ) -> String {
+        match self.num_replicates() {
+            1 => input_variant.key_short(),
+            _ => format!("{}/r:{}", input_variant.key_short(), replicate + 1),
+        }
+    }
+
+    /// Long key of the treatment, or run, on the `replicate`-th instance of the input defined by the
+    /// `input_variant` with the algorithm defined by the `algorithm_variant`.
+    ///
+    /// It is equal to the [`run_key_long`] when there exists a single replicate of each input.
+    ///
+    /// [`run_key_long`]: ExperimentSealed::run_key_long
+    fn instance_key_long(
+        &self,
+        input_variant: &Self::InputFactors,
+        replicate: usize,
+        alg_variant: &Self::AlgFactors,
+    ) -> String {
+        match self.num_replicates() {
+            1 => self.run_key_long(input_variant, alg_variant),
+            _ => format!(
+                "{}/replicate:{}/{}",
+                input_variant.key_long(),
+                replicate + 1,
+                alg_variant.key_long()
+            ),
+        }
+    }
+
+    /// Short key of the treatment, or run, on the `replicate`-th instance of the input defined by the
+    /// `input_variant` with the algorithm defined by the `algorithm_variant`.
+    ///
+    /// It is equal to the [`run_key_short`] when there exists a single replicate of each input.
+    ///
+    /// [`run_key_short`]: ExperimentSealed::run_key_short
+    fn instance_key_short(
+        &self,
+        input_variant: &Self::InputFactors,
+        replicate: usize,
+        alg_variant: &Self::AlgFactors,
+    ) -> String {
+        match self.num_replicates() {
+            1 => self.run_key_short(input_variant, alg_variant),
+            _ => format!(
+                "{}/r:{}/{}",
+                input_variant.key_short(),
+                replicate + 1,
+                alg_variant.key_short()
+            ),
+        }
+    }
+
     /// Path of the "estimates.json" file that criterion will create when the benchmark is created,
-    /// for the particular treatment defined by the given `input_variant` and `alg_variant`.
+    /// for the particular treatment defined by the given `input_variant` and `alg_variant` on the
+    /// `replicate`-th instance of the input.
     fn run_estimates_path(
         &self,
         bench_name: &str,
         input_variant: &Self::InputFactors,
+        replicate: usize,
+        alg_variant: &Self::AlgFactors,
+    ) -> PathBuf {
+        let mut path = match self.benchmark_parameter(input_variant) {
+            // the benchmark group of the flat registration is the experiment directory
+            None => self
+                .experiment_dir(bench_name)
+                .join(filename_safe(&self.instance_key_short(
+                    input_variant,
+                    replicate,
+                    alg_variant,
+                ))),
+            Some(parameter) => {
+                let group_id = self.benchmark_group_id(bench_name, input_variant, replicate);
+                ["target", "criterion", &filename_safe(&group_id)]
+                    .iter()
+                    .collect::<PathBuf>()
+                    .join(filename_safe(&alg_variant.key_short()))
+                    .join(filename_safe(&parameter))
+            }
+        };
+        path.push("new");
+        path.push("estimates.json");
+        path
+    }
+
+    /// Id of the criterion benchmark group of the treatments on the `replicate`-th instance of the input
+    /// defined by the `input_variant`, which depends on the [`registration`] of the experiment.
+    ///
+    /// [`registration`]: Experiment::registration
+    fn benchmark_group_id(
+        &self,
+        bench_name: &str,
+        input_variant: &Self::InputFactors,
+        replicate: usize,
+    ) -> String {
+        let Registration::Parameterized { input_factor } = self.registration() else {
+            return bench_name.to_string();
+        };
+
+        let (names, levels): (Vec<_>, Vec<_>) = Self::InputFactors::factor_names()
+            .into_iter()
+            .zip(Self::InputFactors::factor_names_short())
+            .zip(input_variant.factor_levels_short())
+            .filter(|((name, _), _)| *name != input_factor)
+            .map(|((_, name_short), level)| (name_short, level))
+            .unzip();
+
+        let mut id = bench_name.to_string();
+        if !names.is_empty() {
+            id.push('/');
+            id.push_str(&join(&names, &levels));
+        }
+        if self.num_replicates() > 1 {
+            id.push_str(&format!("/r:{}", replicate + 1));
+        }
+        id
+    }
+
+    /// Parameter of the criterion benchmark ids of the treatments on the input defined by the
+    /// `input_variant`, which is the level of the input factor of the [`Registration::Parameterized`]
+    /// registration; None for the [`Registration::Flat`] registration.
+    fn benchmark_parameter(&self, input_variant: &Self::InputFactors) -> Option<String> {
+        let Registration::Parameterized { input_factor } = self.registration() else {
+            return None;
+        };
+        Self::InputFactors::factor_names()
+            .into_iter()
+            .zip(input_variant.factor_levels())
+            .find(|(name, _)| *name == input_factor)
+            .map(|(_, level)| level)
+    }
+
+    /// Returns an error if the input factor of the [`Registration::Parameterized`] registration is not one of the
+    /// input [`factor_names`].
+    ///
+    /// [`factor_names`]: Factors::factor_names
+    fn validate_registration(&self) -> Result<(), Error> {
+        match self.registration() {
+            Registration::Parameterized { input_factor }
+                if !Self::InputFactors::factor_names().contains(&input_factor) =>
+            {
+                Err(Error::UnknownInputFactor {
+                    input_factor,
+                    factor_names: Self::InputFactors::factor_names(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the `(k, a)` indices of the treatments in their order of execution, where k is the index of the
+    /// instance of the input and a is the index of the algorithm variant.
+    ///
+    /// The order is determined by the [`treatment_order`].
+    /// In addition, with the [`Registration::Parameterized`] registration, treatments of the same benchmark
+    /// group are executed consecutively; groups are ordered by their first treatment.
+    ///
+    /// [`treatment_order`]: Experiment::treatment_order
+    fn execution_order(
+        &self,
+        bench_name: &str,
+        input_levels: &[Self::InputFactors],
+        alg_levels: &[Self::AlgFactors],
+    ) -> Vec<(usize, usize)> {
+        let num_r = self.num_replicates();
+        let num_instances = input_levels.len() * num_r;
+        let mut order = self
+            .treatment_order()
+            .execution_order(num_instances, alg_levels.len());
+
+        if let Registration::Parameterized { .. } = self.registration() {
+            let group_ids: Vec<_> = (0..num_instances)
+                .map(|k| self.benchmark_group_id(bench_name, &input_levels[k / num_r], k % num_r))
+                .collect();
+            let mut groups: Vec<&String> = vec![];
+            for &(k, _) in &order {
+                if !groups.contains(&&group_ids[k]) {
+                    groups.push(&group_ids[k]);
+                }
+            }
+            order.sort_by_key(|&(k, _)| groups.iter().position(|x| **x == group_ids[k]));
+        }
+
+        order
+    }
+
+    /// Path of the file containing the debug representation of the `kind` ("expected" or "actual")
+    /// output of the treatment defined by the given `input_variant` and `alg_variant` on the `replicate`-th
+    /// instance of the input, which is written when the outputs do not match.
+    fn mismatch_path(
+        &self,
+        bench_name: &str,
+        input_variant: &Self::InputFactors,
+        replicate: usize,
         alg_variant: &Self::AlgFactors,
+        kind: &str,
     ) -> PathBuf {
-        let execution_path = self
-            .run_key_short(input_variant, alg_variant)
-            .replace("/", "_")
-            .replace(":", "_");
-        [
-            "target",
-            "criterion",
-            bench_name,
-            &execution_path,
-            "new",
-            "estimates.json",
-        ]
-        .iter()
-        .collect()
+        // not truncated as criterion does, since the file of each treatment must be distinct
+        let execution_path =
+            replace_special_chars(&self.instance_key_short(input_variant, replicate, alg_variant));
+        self.experiment_dir(bench_name)
+            .join("mismatches")
+            .join(format!("{execution_path}_{kind}.txt"))
+    }
+
+    /// Path of the file caching the `replicate`-th instance of the input defined by the `input_variant`.
+    fn input_cache_path(
+        &self,
+        bench_name: &str,
+        input_variant: &Self::InputFactors,
+        replicate: usize,
+    ) -> PathBuf {
+        // not truncated as criterion does, since the file of each instance must be distinct
+        let input_path =
+            replace_special_chars(&self.input_instance_key_short(input_variant, replicate));
+        self.experiment_dir(bench_name)
+            .join("inputs")
+            .join(format!("{input_path}.bin"))
+    }
+
+    /// Directory of the experiment, where the summary files are created; this is also the directory of the
+    /// criterion benchmark group of the treatments with the flat [`Registration`].
+    fn experiment_dir(&self, bench_name: &str) -> PathBuf {
+        ["target", "criterion", &filename_safe(bench_name)]
+            .iter()
+            .collect()
+    }
+
+    /// Path of the `{kind}_{bench_name}.{extension}` file in the [`experiment_dir`], where the `bench_name` is
+    /// made file name safe.
+    ///
+    /// [`experiment_dir`]: ExperimentSealed::experiment_dir
+    fn experiment_file_path(&self, bench_name: &str, kind: &str, extension: &str) -> PathBuf {
+        let name = format!("{kind}_{}.{extension}", filename_safe(bench_name));
+        self.experiment_dir(bench_name).join(name)
     }
 
     /// Path of the benchmark file including this experiment.
@@ -54,27 +291,57 @@ pub trait ExperimentSealed: Experiment {
     /// Path of the csv file containing the summary table that will be created at the end of the
     /// benchmark execution.
     fn summary_csv_path(&self, bench_name: &str) -> PathBuf {
-        [
-            "target",
-            "criterion",
-            bench_name,
-            &format!("summary_{bench_name}.csv"),
-        ]
-        .iter()
-        .collect()
+        self.experiment_file_path(bench_name, "summary", "csv")
+    }
+
+    /// Path of the csv file containing the summary table in the long format, which will be created at the end
+    /// of the benchmark execution when the [`long_format`] is enabled.
+    ///
+    /// [`long_format`]: crate::CsvFormat::long_format
+    fn summary_long_csv_path(&self, bench_name: &str) -> PathBuf {
+        self.experiment_file_path(bench_name, "summary_long", "csv")
+    }
+
+    /// Path of the csv file containing the times of the treatments in the pivot layout, with inputs as rows and
+    /// algorithm variants as columns, which will be created at the end of the benchmark execution.
+    fn pivot_csv_path(&self, bench_name: &str) -> PathBuf {
+        self.experiment_file_path(bench_name, "pivot", "csv")
+    }
+
+    /// Path of the markdown file containing the report of the experiment which will be created at the end of
+    /// the benchmark execution.
+    fn report_path(&self, bench_name: &str) -> PathBuf {
+        self.experiment_file_path(bench_name, "report", "md")
+    }
+
+    /// Path of the json file containing the summary of the experiment that will be created at the end of the
+    /// benchmark execution.
+    fn summary_json_path(&self, bench_name: &str) -> PathBuf {
+        self.experiment_file_path(bench_name, "summary", "json")
+    }
+
+    /// Path of the csv file containing the Pareto-optimal algorithm variants over time and metrics
+    /// which will be created at the end of the benchmark execution.
+    fn pareto_csv_path(&self, bench_name: &str) -> PathBuf {
+        self.experiment_file_path(bench_name, "pareto", "csv")
+    }
+
+    /// Path of the csv file containing the execution order of the treatments, which is created before the
+    /// benchmark execution when the treatments are executed in a random order.
+    fn order_csv_path(&self, bench_name: &str) -> PathBuf {
+        self.experiment_file_path(bench_name, "order", "csv")
+    }
+
+    /// Path of the csv file containing the time estimates of the treatments on each replicate of the inputs,
+    /// which will be created at the end of the benchmark execution when inputs are replicated.
+    fn replicates_csv_path(&self, bench_name: &str) -> PathBuf {
+        self.experiment_file_path(bench_name, "replicates", "csv")
     }
 
     /// Path of the markdown file containing a draft AI prompt to analyze the summary file which
     /// will also be created at the end of the benchmark execution.
     fn ai_prompt_path(&self, bench_name: &str) -> PathBuf {
-        [
-            "target",
-            "criterion",
-            bench_name,
-            &format!("prompt_{bench_name}.md"),
-        ]
-        .iter()
-        .collect()
+        self.experiment_file_path(bench_name, "prompt", "md")
     }
 }
 
@@ -0,0 +1,31 @@
+use crate::Experiment;
+use colorize::AnsiColor;
+
+/// Logs the `output` of the lifecycle `hook` if it returned any.
+pub(crate) fn log_hook(hook: &str, output: Option<String>) {
+    if let Some(output) = output {
+        let log = format!("[{hook}] {output}");
+        println!("{}", log.italic());
+    }
+}
+
+/// Drops the instances of the inputs all treatments of which are completed, and calls the [`after_input`]
+/// hook for each of them.
+///
+/// Instance `k` of the `inputs` is the replicate `k % num_replicates` of the input `k / num_replicates`.
+///
+/// [`after_input`]: crate::Experiment::after_input
+pub(crate) fn release_inputs<E: Experiment>(
+    exp: &mut E,
+    input_levels: &[E::InputFactors],
+    inputs: &mut [Option<E::Input>],
+    num_remaining: &[usize],
+) {
+    let num_r = exp.num_replicates();
+    for (k, input) in inputs.iter_mut().enumerate() {
+        if num_remaining[k] == 0 && input.is_some() {
+            *input = None;
+            log_hook("after_input", exp.after_input(&input_levels[k / num_r]));
+        }
+    }
+}
@@ -14,10 +14,54 @@
 #[cfg(test)]
 mod tests;
 
+mod alloc;
+mod cache;
+mod config;
+mod csv;
+mod error;
+mod estimate;
+mod execution;
 mod experiment;
 mod experiment_sealed;
 mod factors;
+mod hooks;
+mod measurement;
+mod mismatch;
+mod order;
+mod pareto;
+mod phases;
+mod pivot;
+mod plan;
+mod random;
+mod record;
+mod registration;
+mod replicate;
+mod report;
+mod result;
+mod seed;
 mod summary;
+mod summary_json;
+mod throughput;
+mod tolerance;
 
+pub use alloc::{AllocationStats, CountingAllocator, measure_allocations};
+pub use cache::{CachedInput, InputCache};
+pub use config::TreatmentConfig;
+pub use csv::CsvFormat;
+pub use error::Error;
+pub use execution::ExecutionMode;
 pub use experiment::Experiment;
 pub use factors::Factors;
+pub use measurement::ExperimentMeasurement;
+pub use mismatch::debug_diff;
+pub use order::TreatmentOrder;
+pub use pareto::Objective;
+pub use phases::PhaseTimer;
+pub use pivot::SummaryLayout;
+pub use plan::{ExperimentPlan, PLAN_ENV_VAR, TreatmentPlan};
+pub use record::TreatmentStatus;
+pub use registration::Registration;
+pub use replicate::Replicate;
+pub use result::{ExperimentResult, TreatmentResult, Validation};
+pub use seed::derive_seed;
+pub use tolerance::Tolerance;
@@ -0,0 +1,62 @@
+use criterion::measurement::{Measurement, WallTime};
+
+/// A criterion [`Measurement`] which can be used to run an [`Experiment`].
+///
+/// In addition to the measurement itself, the experiment requires to create an instance of the measurement
+/// in order to label the unit of the measured values in the summary tables by its formatter.
+/// Further, since criterion does not expose the measurement of its runner, the total time of the executions
+/// of the treatments with [`phases`] is measured by the instance returned by [`create`]; it must therefore
+/// measure exactly as the measurement configured on the criterion runner.
+///
+/// It is implemented for criterion's default [`WallTime`] measurement.
+/// Other measurements, such as CPU time or hardware counters, can be used by implementing this trait.
+/// When the measurement is defined in another crate, it can be wrapped in a new type implementing both
+/// [`Measurement`] by delegation and this trait.
+///
+/// [`Experiment`]: crate::Experiment
+/// [`phases`]: crate::Experiment::phases
+/// [`create`]: ExperimentMeasurement::create
+///
+/// # Examples
+///
+/// ```ignore
+/// impl ExperimentMeasurement for CpuTime {
+///     fn create() -> Self {
+///         CpuTime
+///     }
+/// }
+///
+/// fn run(c: &mut Criterion<CpuTime>) {
+///     SearchExp.bench(c, "search_cpu_time", &input_levels, &alg_levels);
+/// }
+///
+/// criterion_group! {
+///     name = benches;
+///     config = Criterion::default().with_measurement(CpuTime);
+///     targets = run
+/// }
+/// ```
+pub trait ExperimentMeasurement: Measurement + Sized {
+    /// Creates an instance of the measurement.
+    ///
+    /// The instance is used in place of the measurement of the criterion runner to measure the executions of
+    /// the treatments with phases; hence, it must be configured identically, such as measuring the same
+    /// hardware counter.
+    fn create() -> Self;
+
+    /// Unit of the values of the measurement as reported by its formatter; such as "ns" for [`WallTime`].
+    fn unit() -> &'static str {
+        Self::create().formatter().scale_for_machines(&mut [])
+    }
+}
+
+impl ExperimentMeasurement for WallTime {
+    fn create() -> Self {
+        WallTime
+    }
+}
+
+/// Title of the summary column of the measured values of the treatments.
+pub(crate) fn time_title<M: ExperimentMeasurement>() -> String {
+    format!("Time ({})", M::unit())
+}
@@ -0,0 +1,148 @@
+use crate::Experiment;
+use crate::experiment_sealed::ExperimentSealed;
+use crate::summary::create_file;
+use std::fmt::Debug;
+use std::io::Write;
+
+/// Number of lines to display before and after the first differing line.
+const CONTEXT_LINES: usize = 3;
+
+/// Maximum number of characters of a line to display.
+const MAX_LINE_LEN: usize = 120;
+
+/// Description of the mismatch of values which have identical debug representations.
+const IDENTICAL_DEBUG: &str = "debug representations of the expected and actual values are identical; they differ in a way that is not visible in their debug output, such as NaN values or differences below the printed precision";
+
+/// Creates a bounded, human-readable description of the difference between the `expected` and `actual`
+/// values, rather than dumping their entire debug representations.
+///
+/// The values are compared by their pretty debug representations (`{:#?}`) line by line.
+/// The description contains:
+///
+/// * number of lines of both representations,
+/// * the first differing line, and the index of the differing element when the values are sequences,
+/// * a window of a few lines around the first difference for both values.
+///
+/// When the debug representations are identical, although the values are not considered to match, such as
+/// when they contain NaN values, the description states this instead.
+///
+/// It is used by the default implementation of [`outputs_match`], and can be used while overwriting it.
+///
+/// [`outputs_match`]: crate::Experiment::outputs_match
+///
+/// # Examples
+///
+/// ```
+/// use orx_criterion::*;
+///
+/// let expected: Vec<usize> = (0..1000).collect();
+/// let mut actual = expected.clone();
+/// actual[500] = 42;
+///
+/// let diff = debug_diff(&expected, &actual);
+/// assert!(diff.contains("first difference at line 502 (element at index 500)"));
+/// assert!(diff.contains("expected: 1002 lines, actual: 1002 lines"));
+/// assert!(diff.lines().count() < 20);
+/// ```
+pub fn debug_diff<T: Debug>(expected: &T, actual: &T) -> String {
+    let expected = format!("{expected:#?}");
+    let actual = format!("{actual:#?}");
+    if expected == actual {
+        return IDENTICAL_DEBUG.to_string();
+    }
+    let expected: Vec<_> = expected.lines().collect();
+    let actual: Vec<_> = actual.lines().collect();
+
+    let first_diff = expected
+        .iter()
+        .zip(&actual)
+        .position(|(e, a)| e != a)
+        .unwrap_or(expected.len().min(actual.len()));
+
+    let mut diff = format!("first difference at line {}", first_diff + 1);
+    if let Some(index) = element_index(&expected, first_diff) {
+        diff.push_str(&format!(" (element at index {index})"));
+    }
+    diff.push_str(&format!(
+        "\nlengths of debug representations; expected: {} lines, actual: {} lines",
+        expected.len(),
+        actual.len()
+    ));
+
+    for (title, lines) in [("expected", &expected), ("actual", &actual)] {
+        diff.push_str(&format!("\n--- {title}"));
+        let begin = first_diff.saturating_sub(CONTEXT_LINES);
+        let end = (first_diff + CONTEXT_LINES + 1).min(lines.len());
+        for (l, line) in lines.iter().enumerate().take(end).skip(begin) {
+            let marker = match l == first_diff {
+                true => '>',
+                false => ' ',
+            };
+            diff.push_str(&format!("\n{marker} {:>6} | {}", l + 1, truncate(line)));
+        }
+        if first_diff >= lines.len() {
+            diff.push_str("\n>        | <end>");
+        }
+    }
+
+    diff
+}
+
+/// Index of the top level element containing the line at `line` when the debug representation
+/// belongs to a sequence; None otherwise.
+fn element_index(lines: &[&str], line: usize) -> Option<usize> {
+    let is_sequence = lines.first().is_some_and(|x| *x == "[");
+    match is_sequence && line > 0 && line + 1 < lines.len() {
+        true => {
+            let is_element_begin = |x: &&&str| {
+                x.starts_with("    ")
+                    && !x.starts_with("     ")
+                    && !x[4..].starts_with(['}', ']', ')'])
+            };
+            let num_elements = lines[1..=line].iter().filter(is_element_begin).count();
+            num_elements.checked_sub(1)
+        }
+        false => None,
+    }
+}
+
+fn truncate(line: &str) -> String {
+    match line.chars().count() > MAX_LINE_LEN {
+        true => {
+            let truncated: String = line.chars().take(MAX_LINE_LEN).collect();
+            format!("{truncated}...")
+        }
+        false => line.to_string(),
+    }
+}
+
+/// Writes the full debug representations of the `expected` and `actual` outputs of the treatment
+/// defined by `input_variant` and `alg_variant` on the `replicate`-th instance of the input to files
+/// under the experiment directory.
+///
+/// Returns the message referring to the written files, which further notes when the debug representations
+/// are identical.
+pub(crate) fn write_mismatch_files<E: Experiment>(
+    exp: &E,
+    name: &str,
+    input_variant: &E::InputFactors,
+    replicate: usize,
+    alg_variant: &E::AlgFactors,
+    expected: &E::Output,
+    actual: &E::Output,
+) -> std::io::Result<String> {
+    let expected_path = exp.mismatch_path(name, input_variant, replicate, alg_variant, "expected");
+    let actual_path = exp.mismatch_path(name, input_variant, replicate, alg_variant, "actual");
+    let expected = format!("{expected:#?}");
+    let actual = format!("{actual:#?}");
+
+    for (path, output) in [(&expected_path, &expected), (&actual_path, &actual)] {
+        create_file(path)?.write_all(output.as_bytes())?;
+    }
+
+    let mut message = format!("Full outputs are written to:\n{expected_path:?}\n{actual_path:?}");
+    if expected == actual {
+        message.push_str(&format!("\nNote that the {IDENTICAL_DEBUG}."));
+    }
+    Ok(message)
+}
@@ -0,0 +1,115 @@
+use crate::Experiment;
+use crate::experiment_sealed::ExperimentSealed;
+use crate::random::SplitMix64;
+use crate::summary::create_file;
+use std::io::Write;
+
+/// Order in which the treatments of the experiment are executed.
+///
+/// Regardless of the execution order, the summary tables always present the treatments in the canonical order.
+///
+/// Each input is created once, right before its first treatment is executed, and dropped once all of its
+/// treatments are completed.
+/// Therefore, with the [`Canonical`] and [`BlockedRandomized`] orders, only one input is alive at a time;
+/// while with the [`Randomized`] order, many or all of the inputs might be alive at the same time.
+///
+/// [`Canonical`]: TreatmentOrder::Canonical
+/// [`BlockedRandomized`]: TreatmentOrder::BlockedRandomized
+/// [`Randomized`]: TreatmentOrder::Randomized
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TreatmentOrder {
+    /// Inputs are executed in the outer loop and algorithm variants in the inner loop, both in the order they
+    /// are provided.
+    ///
+    /// A drift in the environment during the experiment, such as thermal throttling or changing background load,
+    /// systematically penalizes later inputs and variants.
+    #[default]
+    Canonical,
+    /// All treatments are executed in a random order determined by the `seed`.
+    Randomized {
+        /// Seed of the random order.
+        seed: u64,
+    },
+    /// Inputs are executed in a random order, and algorithm variants of each input are executed in a random
+    /// order in a block, where the order is determined by the `seed`.
+    ///
+    /// The drift between variants is randomized, while each input is created only once and released after its
+    /// block is completed.
+    BlockedRandomized {
+        /// Seed of the random order.
+        seed: u64,
+    },
+}
+
+impl TreatmentOrder {
+    /// Returns the `(i, a)` indices of the treatments in their order of execution, where `num_inputs` and
+    /// `num_variants` are the numbers of inputs and algorithm variants.
+    pub(crate) fn execution_order(
+        &self,
+        num_inputs: usize,
+        num_variants: usize,
+    ) -> Vec<(usize, usize)> {
+        let canonical = |i| (0..num_variants).map(move |a| (i, a));
+        match *self {
+            TreatmentOrder::Canonical => (0..num_inputs).flat_map(canonical).collect(),
+            TreatmentOrder::Randomized { seed } => {
+                let mut order: Vec<_> = (0..num_inputs).flat_map(canonical).collect();
+                SplitMix64::new(seed).shuffle(&mut order);
+                order
+            }
+            TreatmentOrder::BlockedRandomized { seed } => {
+                let mut rng = SplitMix64::new(seed);
+                let mut inputs: Vec<_> = (0..num_inputs).collect();
+                rng.shuffle(&mut inputs);
+                let mut order = vec![];
+                for i in inputs {
+                    let mut block: Vec<_> = canonical(i).collect();
+                    rng.shuffle(&mut block);
+                    order.extend(block);
+                }
+                order
+            }
+        }
+    }
+}
+
+/// Writes the execution `order` of the treatments on the instances of the inputs to a csv file under the
+/// experiment directory, together with the seed of the `treatment_order` that determines the order; the seed
+/// is "NA" for the canonical order.
+pub(crate) fn create_order_csv<E: Experiment>(
+    exp: &E,
+    name: &str,
+    input_levels: &[E::InputFactors],
+    alg_levels: &[E::AlgFactors],
+    treatment_order: TreatmentOrder,
+    order: &[(usize, usize)],
+) -> std::io::Result<()> {
+    let mut file = create_file(&exp.order_csv_path(name))?;
+    let seed = match treatment_order {
+        TreatmentOrder::Canonical => "NA".to_string(),
+        TreatmentOrder::Randomized { seed } | TreatmentOrder::BlockedRandomized { seed } => {
+            seed.to_string()
+        }
+    };
+
+    let format = exp.csv_format();
+    let titles = ["order", "t", "i", "r", "a", "treatment", "seed"];
+    file.write_all(format.row(&titles).as_bytes())?;
+
+    let num_r = exp.num_replicates();
+    for (idx, (k, a)) in order.iter().enumerate() {
+        let (i, r) = (k / num_r, k % num_r);
+        let t = i * alg_levels.len() + a;
+        let row = [
+            (idx + 1).to_string(),
+            (t + 1).to_string(),
+            (i + 1).to_string(),
+            (r + 1).to_string(),
+            (a + 1).to_string(),
+            exp.instance_key_short(&input_levels[i], r, &alg_levels[*a]),
+            seed.clone(),
+        ];
+        file.write_all(format.row(&row).as_bytes())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use crate::Experiment;
+use crate::record::TreatmentRecord;
+
+/// Direction of optimization of a response of the experiment.
+///
+/// Time is always minimized, while the objective of each metric defined by [`metrics`] is determined
+/// by [`metric_objective`].
+///
+/// [`metrics`]: crate::Experiment::metrics
+/// [`metric_objective`]: crate::Experiment::metric_objective
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Objective {
+    /// Smaller values of the response are better.
+    Minimize,
+    /// Larger values of the response are better.
+    Maximize,
+}
+
+/// Pareto-optimal algorithm variants over time and metrics.
+pub(crate) struct ParetoFronts {
+    /// `per_input[i][a]` is true if algorithm variant `a` is Pareto-optimal for the input `i`.
+    pub per_input: Vec<Vec<bool>>,
+    /// `overall[a]` is true if algorithm variant `a` is Pareto-optimal across all inputs.
+    pub overall: Vec<bool>,
+}
+
+impl ParetoFronts {
+    /// Computes the Pareto fronts over (time, metrics) of the treatments defined by the `estimates` and `records`.
+    ///
+    /// Across inputs, a variant dominates another if it is no worse in every response for every input, and
+    /// strictly better in at least one.
+    /// This avoids comparing aggregated responses of inputs of different scales.
+    pub fn new<E: Experiment>(
+        exp: &E,
+        metric_names: &[&'static str],
+        estimates: &[Vec<Option<f64>>],
+        records: &[Vec<TreatmentRecord>],
+    ) -> Self {
+        let signs: Vec<_> = metric_names
+            .iter()
+            .map(|m| match exp.metric_objective(m) {
+                Objective::Minimize => 1.0,
+                Objective::Maximize => -1.0,
+            })
+            .collect();
+
+        let points: Vec<Vec<Option<Vec<f64>>>> = estimates
+            .iter()
+            .zip(records)
+            .map(|(input_estimates, input_records)| {
+                input_estimates
+                    .iter()
+                    .zip(input_records)
+                    .map(|(estimate, record)| {
+                        let mut point = vec![(*estimate)?];
+                        for (metric, sign) in metric_names.iter().zip(&signs) {
+                            point.push(sign * record.metric(metric)?);
+                        }
+                        Some(point)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let per_input = points.iter().map(|x| pareto_front(x)).collect();
+        let overall = pareto_front_across_inputs(&points);
+
+        Self { per_input, overall }
+    }
+}
+
+/// Returns whether or not the point `a` dominates the point `b`, where all responses are minimized.
+pub(crate) fn dominates(a: &[f64], b: &[f64]) -> bool {
+    a.iter().zip(b).all(|(x, y)| x <= y) && a.iter().zip(b).any(|(x, y)| x < y)
+}
+
+/// Returns whether or not each of the `points` is Pareto-optimal, where all responses are minimized.
+///
+/// Missing points are never Pareto-optimal.
+pub(crate) fn pareto_front(points: &[Option<Vec<f64>>]) -> Vec<bool> {
+    points
+        .iter()
+        .map(|point| match point {
+            Some(p) => !points.iter().flatten().any(|q| dominates(q, p)),
+            None => false,
+        })
+        .collect()
+}
+
+/// Returns whether or not each algorithm variant is Pareto-optimal across all inputs, where `points[i][a]` is the
+/// point of variant `a` for input `i`.
+///
+/// Variants with a missing point for any of the inputs are never Pareto-optimal.
+pub(crate) fn pareto_front_across_inputs(points: &[Vec<Option<Vec<f64>>>]) -> Vec<bool> {
+    let num_a = points.first().map(|x| x.len()).unwrap_or(0);
+    let concatenated = (0..num_a).map(|a| -> Option<Vec<f64>> {
+        let mut point = vec![];
+        for input_points in points {
+            point.extend_from_slice(input_points[a].as_deref()?);
+        }
+        Some(point)
+    });
+    pareto_front(&concatenated.collect::<Vec<_>>())
+}
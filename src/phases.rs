@@ -0,0 +1,117 @@
+use std::time::{Duration, Instant};
+
+/// A timer handle passed to [`execute_phased`] which allows the execution to report the time spent in its
+/// named phases.
+///
+/// A phase starts with a [`start`] call and lasts until the next phase starts, [`stop`] is called or the
+/// execution completes.
+///
+/// [`execute_phased`]: crate::Experiment::execute_phased
+/// [`start`]: PhaseTimer::start
+/// [`stop`]: PhaseTimer::stop
+///
+/// # Examples
+///
+/// ```
+/// use orx_criterion::*;
+///
+/// let mut timer = PhaseTimer::new(vec!["build", "query"]);
+///
+/// timer.start("build");
+/// let set: std::collections::HashSet<_> = (0..1000).collect();
+/// timer.start("query");
+/// let found = (0..1000).filter(|x| set.contains(&(x * 2))).count();
+/// timer.stop();
+///
+/// assert_eq!(found, 500);
+///
+/// let durations = timer.durations();
+/// assert_eq!(durations.len(), 2);
+/// assert_eq!(durations[0].0, "build");
+/// assert_eq!(durations[1].0, "query");
+/// ```
+///
+/// The default timer is disabled: it neither measures the phases nor allocates, which makes it suitable to
+/// implement [`execute`] by [`execute_phased`] without affecting its time or allocations.
+///
+/// [`execute`]: crate::Experiment::execute
+///
+/// ```
+/// use orx_criterion::*;
+///
+/// let mut timer = PhaseTimer::default();
+///
+/// timer.start("build");
+/// timer.stop();
+///
+/// assert!(timer.durations().is_empty());
+/// ```
+#[derive(Debug, Default)]
+pub struct PhaseTimer {
+    durations: Vec<(&'static str, Duration)>,
+    current: Option<(usize, Instant)>,
+    num_executions: u64,
+    enabled: bool,
+}
+
+impl PhaseTimer {
+    /// Creates a new timer for the given `phases`.
+    ///
+    /// Phases which are not in the list can still be started, and they are appended to the list in order
+    /// of their first start.
+    pub fn new(phases: Vec<&'static str>) -> Self {
+        Self {
+            durations: phases.into_iter().map(|x| (x, Duration::ZERO)).collect(),
+            current: None,
+            num_executions: 0,
+            enabled: true,
+        }
+    }
+
+    /// Ends the current phase, if any, and starts the `phase` with the given name; does nothing if the timer
+    /// is disabled.
+    pub fn start(&mut self, phase: &'static str) {
+        if !self.enabled {
+            return;
+        }
+        self.stop();
+        let idx = match self.durations.iter().position(|(x, _)| *x == phase) {
+            Some(idx) => idx,
+            None => {
+                self.durations.push((phase, Duration::ZERO));
+                self.durations.len() - 1
+            }
+        };
+        self.current = Some((idx, Instant::now()));
+    }
+
+    /// Ends the current phase, if any.
+    pub fn stop(&mut self) {
+        if let Some((idx, begin)) = self.current.take() {
+            self.durations[idx].1 += begin.elapsed();
+        }
+    }
+
+    /// Total durations spent in each of the phases since the timer is created.
+    pub fn durations(&self) -> &[(&'static str, Duration)] {
+        &self.durations
+    }
+
+    /// Ends the current phase of the execution which is just completed.
+    pub(crate) fn end_execution(&mut self) {
+        self.stop();
+        self.num_executions += 1;
+    }
+
+    /// Mean time in nanoseconds spent in each phase per execution; empty if no execution is completed.
+    pub(crate) fn mean_durations(&self) -> Vec<(&'static str, f64)> {
+        match self.num_executions {
+            0 => vec![],
+            n => self
+                .durations
+                .iter()
+                .map(|(x, d)| (*x, d.as_nanos() as f64 / n as f64))
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use crate::experiment_sealed::ExperimentSealed;
+use crate::measurement::time_title;
+use crate::record::format_time;
+use crate::summary::create_file;
+use crate::{Experiment, ExperimentMeasurement, ExperimentResult, Factors, TreatmentResult};
+use cli_table::{Cell, CellStruct, Color, Style, Table, format::Justify, print_stdout};
+use colorize::AnsiColor;
+use std::io::Write;
+
+/// Layout of the summary table printed to the console at the end of the experiment, which is determined by
+/// [`summary_layout`].
+///
+/// [`summary_layout`]: crate::Experiment::summary_layout
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SummaryLayout {
+    /// Each row is a treatment listing the levels of its input and algorithm factors together with its time,
+    /// metrics and other statistics.
+    ///
+    /// This is the default layout.
+    #[default]
+    Table,
+    /// Each row is an input and each column is an algorithm variant identified by its short key, where the
+    /// cells are the times of the treatments colored by their ranks on the input.
+    /// The last row aggregates each variant across all inputs by the geometric mean of its time relative to
+    /// the fastest variant of each input.
+    ///
+    /// This layout is compact when there are many algorithm variants; however, it lists only the times.
+    Pivot,
+    /// Both the table and the pivot layouts are printed.
+    Both,
+}
+
+impl SummaryLayout {
+    /// Returns whether or not the table layout is printed.
+    pub(crate) fn has_table(&self) -> bool {
+        matches!(self, Self::Table | Self::Both)
+    }
+
+    /// Returns whether or not the pivot layout is printed.
+    pub(crate) fn has_pivot(&self) -> bool {
+        matches!(self, Self::Pivot | Self::Both)
+    }
+}
+
+/// Title of the aggregate row of the pivot table.
+const AGGREGATE_TITLE: &str = "Relative (geo mean)";
+
+/// String representation of the relative score of a variant to be used in the pivot tables.
+fn format_score(score: Option<f64>) -> String {
+    score.map(|x| format!("{x:.2}")).unwrap_or("NA".to_string())
+}
+
+/// Rank of a cell of the pivot table among the cells of the same row.
+enum Rank {
+    Best,
+    Worst,
+    Intermediate,
+    Missing,
+}
+
+impl Rank {
+    /// Rank of the value with the given 1-based `rank` among `num_ranked` values.
+    fn new(rank: Option<usize>, num_ranked: usize) -> Self {
+        match rank {
+            Some(1) => Self::Best,
+            Some(r) if r == num_ranked => Self::Worst,
+            Some(_) => Self::Intermediate,
+            None => Self::Missing,
+        }
+    }
+
+    fn cell(&self, cell: CellStruct) -> CellStruct {
+        match self {
+            Self::Best => cell.bold(true).foreground_color(Some(Color::Green)),
+            Self::Worst => cell.bold(true).foreground_color(Some(Color::Red)),
+            Self::Intermediate => cell,
+            Self::Missing => cell.foreground_color(Some(Color::Rgb(50, 50, 50))),
+        }
+    }
+}
+
+/// Ranks of the algorithm variants by their relative scores across all inputs, starting from 1 for the best.
+pub(crate) fn score_ranks(scores: &[Option<f64>]) -> Vec<Option<usize>> {
+    let mut ranks = vec![None; scores.len()];
+    let mut measured: Vec<_> = scores
+        .iter()
+        .enumerate()
+        .filter_map(|(a, x)| x.map(|x| (a, x)))
+        .collect();
+    measured.sort_by(|x, y| x.1.total_cmp(&y.1));
+    for (rank, (a, _)) in measured.into_iter().enumerate() {
+        ranks[a] = Some(rank + 1);
+    }
+    ranks
+}
+
+/// Titles of the columns of the pivot tables: the input index, the input factors and the short keys of the
+/// algorithm variants.
+pub(crate) fn pivot_titles<E: Experiment>(result: &ExperimentResult<'_, E>) -> Vec<String> {
+    let mut titles = vec!["i".to_string()];
+    titles.extend(
+        <E::InputFactors as Factors>::factor_names()
+            .into_iter()
+            .map(|x| x.to_string()),
+    );
+    titles.extend(result.alg_levels.iter().map(|x| x.key_short()));
+    titles
+}
+
+/// Cells identifying the input of the `treatments` in the pivot tables.
+fn input_columns<E: Experiment>(treatments: &[TreatmentResult<'_, E>], i: usize) -> Vec<String> {
+    let mut columns = vec![(i + 1).to_string()];
+    if let Some(x) = treatments.first() {
+        columns.extend(x.input.factor_levels());
+    }
+    columns
+}
+
+/// Cells of the aggregate row of the pivot tables, excluding the scores.
+fn aggregate_columns<E: Experiment>() -> Vec<String> {
+    let num_factors = <E::InputFactors as Factors>::factor_names().len();
+    let mut columns = vec![String::new(); num_factors + 1];
+    columns[0] = AGGREGATE_TITLE.to_string();
+    columns
+}
+
+/// Prints the summary in the pivot layout, where rows are the inputs and columns are the algorithm variants.
+pub(crate) fn print_pivot_table<E: Experiment, M: ExperimentMeasurement>(
+    result: &ExperimentResult<'_, E>,
+) -> std::io::Result<()> {
+    let num_a = result.alg_levels.len();
+    let num_input_columns = 1 + <E::InputFactors as Factors>::factor_names().len();
+    let title: Vec<_> = pivot_titles(result)
+        .into_iter()
+        .enumerate()
+        .map(|(j, x)| match j < num_input_columns {
+            true => x.cell().bold(true),
+            false => x.cell().bold(true).justify(Justify::Right),
+        })
+        .collect();
+
+    let mut rows = vec![];
+    for (i, treatments) in result.treatments().chunks(num_a.max(1)).enumerate() {
+        let num_ranked = treatments.iter().filter(|x| x.rank.is_some()).count();
+        let mut columns: Vec<_> = input_columns(treatments, i)
+            .into_iter()
+            .map(|x| x.cell())
+            .collect();
+        for x in treatments {
+            let time = format_time(x.status, x.estimate)
+                .cell()
+                .justify(Justify::Right);
+            columns.push(Rank::new(x.rank, num_ranked).cell(time));
+        }
+        rows.push(columns);
+    }
+
+    let scores = result.relative_scores();
+    let ranks = score_ranks(&scores);
+    let num_ranked = ranks.iter().filter(|x| x.is_some()).count();
+    let mut columns: Vec<_> = aggregate_columns::<E>()
+        .into_iter()
+        .map(|x| x.cell().bold(true))
+        .collect();
+    for (score, rank) in scores.iter().zip(&ranks) {
+        let score = format_score(*score).cell().justify(Justify::Right);
+        columns.push(Rank::new(*rank, num_ranked).cell(score));
+    }
+    rows.push(columns);
+
+    let table = rows.table().title(title);
+    let log = format!("\n# {} - {}", result.name, time_title::<M>());
+    println!("{}", log.bold().yellow());
+    print_stdout(table)
+}
+
+/// Creates the "pivot_{name}.csv" file containing the summary in the pivot layout.
+pub(crate) fn create_pivot_csv<E: Experiment>(
+    exp: &E,
+    result: &ExperimentResult<'_, E>,
+) -> std::io::Result<()> {
+    let format = exp.csv_format();
+    let path = exp.pivot_csv_path(&result.name);
+    let mut file = create_file(&path)?;
+    let num_a = result.alg_levels.len();
+
+    file.write_all(format.row(&pivot_titles(result)).as_bytes())?;
+    for (i, treatments) in result.treatments().chunks(num_a.max(1)).enumerate() {
+        let mut row = input_columns(treatments, i);
+        row.extend(treatments.iter().map(|x| format_time(x.status, x.estimate)));
+        file.write_all(format.row(&row).as_bytes())?;
+    }
+
+    let mut row = aggregate_columns::<E>();
+    row.extend(result.relative_scores().into_iter().map(format_score));
+    file.write_all(format.row(&row).as_bytes())?;
+    Ok(())
+}
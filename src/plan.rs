@@ -0,0 +1,262 @@
+use crate::cache::create_input;
+use crate::experiment_sealed::ExperimentSealed;
+use crate::hooks::log_hook;
+use crate::{Error, Experiment};
+use cli_table::{Cell, Style, Table, format::Justify, print_stdout};
+use colorize::AnsiColor;
+use std::time::{Duration, Instant};
+
+/// Name of the environment variable which switches [`bench`] to the planning mode.
+///
+/// When the variable is set to any value other than "0" or "false", [`bench`] prints the [`ExperimentPlan`]
+/// and returns without measuring; for instance, `ORX_CRITERION_PLAN=1 cargo bench --bench tuning_example`.
+///
+/// Note that criterion does not expose the settings of its runner; hence, the warm-up and measurement times
+/// set on the [`Criterion`] passed to [`bench`] are not visible to the plan, which assumes the criterion
+/// defaults (3 and 5 seconds, respectively) unless the times are set by [`configure`].
+/// Defining the times by [`configure`] rather than on the runner keeps the estimated duration accurate.
+///
+/// [`bench`]: crate::Experiment::bench
+/// [`Criterion`]: criterion::Criterion
+/// [`configure`]: crate::Experiment::configure
+pub const PLAN_ENV_VAR: &str = "ORX_CRITERION_PLAN";
+
+/// Warm-up time of criterion unless configured otherwise.
+const CRITERION_WARM_UP_TIME: Duration = Duration::from_secs(3);
+
+/// Measurement time of criterion unless configured otherwise.
+const CRITERION_MEASUREMENT_TIME: Duration = Duration::from_secs(5);
+
+/// A treatment that will be benchmarked by the experiment, as listed by the [`ExperimentPlan`].
+#[derive(Debug, Clone)]
+pub struct TreatmentPlan {
+    /// Index of the treatment, starting from 1.
+    pub t: usize,
+    /// Index of the input of the treatment, starting from 1.
+    pub i: usize,
+    /// Index of the replicate of the input, starting from 1.
+    pub r: usize,
+    /// Index of the algorithm variant of the treatment, starting from 1.
+    pub a: usize,
+    /// Long key of the treatment, as logged while running the experiment.
+    pub key_long: String,
+    /// Short key of the treatment, which is the criterion benchmark id of the treatment.
+    pub key_short: String,
+    /// Warm-up time of the treatment.
+    pub warm_up_time: Duration,
+    /// Measurement time of the treatment.
+    pub measurement_time: Duration,
+}
+
+/// Plan of an experiment listing the treatments to be benchmarked together with the estimated duration
+/// of the experiment, created by [`plan`].
+///
+/// The estimate is the sum of the warm-up and measurement times of the treatments, and the time to create the
+/// inputs when it is measured; see [`plan_input_times`].
+/// Warm-up and measurement times are obtained from [`configure`], falling back to the criterion defaults
+/// (3 and 5 seconds, respectively) unless they are set for any of the treatments, since the settings of the
+/// criterion runner are not accessible; see [`PLAN_ENV_VAR`].
+/// Note that the estimate is a lower bound since criterion might exceed the measurement time of long
+/// running executions to collect the required number of samples, and it further spends time to analyze
+/// the measurements.
+///
+/// [`plan`]: crate::Experiment::plan
+/// [`configure`]: crate::Experiment::configure
+/// [`plan_input_times`]: crate::Experiment::plan_input_times
+#[derive(Debug, Clone)]
+pub struct ExperimentPlan {
+    /// Name of the benchmark.
+    pub name: String,
+    /// Time spent to create each instance of the inputs, where instances of an input are its replicates;
+    /// `None` unless the inputs are created by the plan.
+    pub input_times: Vec<Option<Duration>>,
+    /// Treatments of the experiment in the order of execution.
+    pub treatments: Vec<TreatmentPlan>,
+    /// Total time budget of the experiment, if any.
+    pub time_budget: Option<Duration>,
+}
+
+impl ExperimentPlan {
+    /// Creates the plan of the experiment `exp` for the given input and algorithm levels.
+    ///
+    /// When [`plan_input_times`] is true, each instance of the inputs is created once in order to measure its
+    /// construction time, between the calls to the [`before_input`] and [`after_input`] hooks.
+    ///
+    /// [`plan_input_times`]: crate::Experiment::plan_input_times
+    /// [`before_input`]: crate::Experiment::before_input
+    /// [`after_input`]: crate::Experiment::after_input
+    pub(crate) fn new<E: Experiment>(
+        exp: &mut E,
+        name: &str,
+        input_levels: &[E::InputFactors],
+        alg_levels: &[E::AlgFactors],
+    ) -> Self {
+        let mut warm_up_time = CRITERION_WARM_UP_TIME;
+        let mut measurement_time = CRITERION_MEASUREMENT_TIME;
+
+        let num_r = exp.num_replicates();
+        let time_inputs = exp.plan_input_times();
+        let input_times = (0..input_levels.len() * num_r)
+            .map(|k| {
+                if !time_inputs {
+                    return None;
+                }
+                let input_variant = &input_levels[k / num_r];
+                log_hook("before_input", exp.before_input(input_variant));
+                let begin = Instant::now();
+                let input = create_input(exp, name, input_variant, k % num_r);
+                let elapsed = begin.elapsed();
+                drop(input);
+                log_hook("after_input", exp.after_input(input_variant));
+                Some(elapsed)
+            })
+            .collect();
+
+        let order = exp.execution_order(name, input_levels, alg_levels);
+        let mut treatments = vec![];
+        let mut group_id = None;
+        for (k, a) in order {
+            let (i, r) = (k / num_r, k % num_r);
+            let (input_variant, alg_variant) = (&input_levels[i], &alg_levels[a]);
+
+            // criterion resets the settings at the beginning of each benchmark group
+            let treatment_group_id = exp.benchmark_group_id(name, input_variant, r);
+            if group_id.as_ref() != Some(&treatment_group_id) {
+                warm_up_time = CRITERION_WARM_UP_TIME;
+                measurement_time = CRITERION_MEASUREMENT_TIME;
+                group_id = Some(treatment_group_id);
+            }
+
+            // criterion keeps the settings of the previous treatments unless they are set again
+            let config = exp.configure(input_variant, alg_variant);
+            warm_up_time = config.warm_up_time.unwrap_or(warm_up_time);
+            measurement_time = config.measurement_time.unwrap_or(measurement_time);
+
+            treatments.push(TreatmentPlan {
+                t: i * alg_levels.len() + a + 1,
+                i: i + 1,
+                r: r + 1,
+                a: a + 1,
+                key_long: exp.instance_key_long(input_variant, r, alg_variant),
+                key_short: exp.instance_key_short(input_variant, r, alg_variant),
+                warm_up_time,
+                measurement_time,
+            });
+        }
+
+        Self {
+            name: name.to_string(),
+            input_times,
+            treatments,
+            time_budget: exp.time_budget(),
+        }
+    }
+
+    /// Estimated total duration of the experiment without considering its time budget.
+    pub fn estimated_duration(&self) -> Duration {
+        let inputs: Duration = self.input_times.iter().flatten().sum();
+        let treatments: Duration = self
+            .treatments
+            .iter()
+            .map(|x| x.warm_up_time + x.measurement_time)
+            .sum();
+        inputs + treatments
+    }
+
+    /// Prints the plan to the console.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the plan table cannot be written to the standard output; see [`try_print`] for the fallible
+    /// version.
+    ///
+    /// [`try_print`]: ExperimentPlan::try_print
+    pub fn print(&self) {
+        self.try_print().expect("Failed to print the plan table");
+    }
+
+    /// Prints the plan to the console; returns an error if the plan table cannot be written to the standard
+    /// output.
+    pub fn try_print(&self) -> Result<(), Error> {
+        let log = format!("\n# Plan of {}", self.name);
+        println!("{}", log.bold().yellow());
+
+        let title = vec![
+            "t".cell().bold(true),
+            "i".cell().bold(true),
+            "r".cell().bold(true),
+            "a".cell().bold(true),
+            "Key".cell().bold(true),
+            "Short Key".cell().bold(true),
+            "Warm-up".cell().bold(true).justify(Justify::Right),
+            "Measurement".cell().bold(true).justify(Justify::Right),
+        ];
+        let rows: Vec<_> = self
+            .treatments
+            .iter()
+            .map(|x| {
+                vec![
+                    x.t.cell(),
+                    x.i.cell(),
+                    x.r.cell(),
+                    x.a.cell(),
+                    x.key_long.as_str().cell(),
+                    x.key_short.as_str().cell(),
+                    format_duration(x.warm_up_time)
+                        .cell()
+                        .justify(Justify::Right),
+                    format_duration(x.measurement_time)
+                        .cell()
+                        .justify(Justify::Right),
+                ]
+            })
+            .collect();
+        print_stdout(rows.table().title(title)).map_err(Error::Print)?;
+
+        let inputs = match self.input_times.iter().all(Option::is_some) {
+            true => format!(
+                "creating the inputs takes {}",
+                format_duration(self.input_times.iter().flatten().sum())
+            ),
+            false => "time to create the inputs is not measured".to_string(),
+        };
+        println!(
+            "\n{} runs over {} input instances; {inputs}.",
+            self.treatments.len(),
+            self.input_times.len(),
+        );
+        let log = format!(
+            "Estimated total duration: {}",
+            format_duration(self.estimated_duration())
+        );
+        println!("{}", log.bold());
+        if let Some(budget) = self.time_budget {
+            println!(
+                "Time budget of the experiment: {}; treatments exceeding the budget will be skipped.",
+                format_duration(budget)
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Returns whether or not the planning mode is switched on by the [`PLAN_ENV_VAR`] environment variable.
+pub(crate) fn is_plan_mode() -> bool {
+    std::env::var(PLAN_ENV_VAR).is_ok_and(|x| !matches!(x.as_str(), "" | "0" | "false"))
+}
+
+/// Human-readable representation of the `duration`, such as "1h 02m 05s" or "850ms".
+pub(crate) fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0 => format!("{}ms", duration.as_millis()),
+        1..60 => format!("{:.1}s", duration.as_secs_f64()),
+        60..3600 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!(
+            "{}h {:02}m {:02}s",
+            secs / 3600,
+            (secs % 3600) / 60,
+            secs % 60
+        ),
+    }
+}
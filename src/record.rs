@@ -0,0 +1,275 @@
+use crate::experiment_sealed::ExperimentSealed;
+use crate::mismatch::write_mismatch_files;
+use crate::replicate::ReplicateStats;
+use crate::{AllocationStats, ExecutionMode, Experiment, measure_allocations};
+use std::time::{Duration, Instant};
+
+/// Status of a treatment of the experiment.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TreatmentStatus {
+    /// The treatment is measured by criterion.
+    #[default]
+    Measured,
+    /// The pilot execution of the treatment exceeded its time limit; and hence, it is not measured.
+    TimedOut,
+    /// The treatment is not executed since the time budget of the experiment is exhausted.
+    Skipped,
+    /// The treatment is not run by criterion, such as when it is excluded by the benchmark filter.
+    NotRun,
+}
+
+/// Data recorded once per instance of an input while running the experiment.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct InputRecord {
+    /// Time elapsed to create, or load from the cache, the instance of the input.
+    pub elapsed: Duration,
+    /// Size of the input in bytes reported by [`input_size_bytes`], if any.
+    ///
+    /// [`input_size_bytes`]: crate::Experiment::input_size_bytes
+    pub size_bytes: Option<usize>,
+}
+
+/// Data recorded once per treatment while running the experiment, in addition to the time estimates
+/// created by criterion.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct TreatmentRecord {
+    /// Status of the treatment.
+    pub status: TreatmentStatus,
+    /// Time elapsed by the single execution of the treatment while recording.
+    pub elapsed: Duration,
+    /// Whether or not the output of the treatment is compared to and matched the [`expected_output`].
+    ///
+    /// [`expected_output`]: crate::Experiment::expected_output
+    pub validated: bool,
+    /// Secondary responses of the treatment computed by [`metrics`].
+    ///
+    /// [`metrics`]: crate::Experiment::metrics
+    pub metrics: Vec<(&'static str, f64)>,
+    /// Allocations made by one `execute` call; available only when the [`CountingAllocator`] is registered
+    /// as the global allocator.
+    ///
+    /// [`CountingAllocator`]: crate::CountingAllocator
+    pub allocations: Option<AllocationStats>,
+    /// Mean time in nanoseconds spent in each phase reported by [`execute_phased`] per execution.
+    ///
+    /// [`execute_phased`]: crate::Experiment::execute_phased
+    pub phases: Vec<(&'static str, f64)>,
+    /// Statistics of the time estimates across the replicates of the input; available only when the inputs
+    /// are replicated.
+    pub replicates: Option<ReplicateStats>,
+    /// Data recorded for the input of the treatment; missing if the input is not created.
+    pub input: Option<InputRecord>,
+}
+
+impl TreatmentRecord {
+    /// Record of a treatment which is skipped since the time budget of the experiment is exhausted.
+    pub fn skipped() -> Self {
+        Self {
+            status: TreatmentStatus::Skipped,
+            ..Default::default()
+        }
+    }
+
+    /// Record of a treatment which is not run by criterion, such as when it is excluded by the benchmark filter.
+    pub fn not_run() -> Self {
+        Self {
+            status: TreatmentStatus::NotRun,
+            ..Default::default()
+        }
+    }
+
+    /// Value of the metric with the given `name`, if recorded.
+    pub fn metric(&self, name: &str) -> Option<f64> {
+        value_of(&self.metrics, name)
+    }
+
+    /// Mean time in nanoseconds spent in the phase with the given `name`, if recorded.
+    pub fn phase(&self, name: &str) -> Option<f64> {
+        value_of(&self.phases, name)
+    }
+}
+
+fn value_of(values: &[(&'static str, f64)], name: &str) -> Option<f64> {
+    values.iter().find(|(n, _)| *n == name).map(|(_, x)| *x)
+}
+
+fn names_of(
+    records: &[Vec<TreatmentRecord>],
+    values: impl Fn(&TreatmentRecord) -> &[(&'static str, f64)],
+) -> Vec<&'static str> {
+    let mut names = vec![];
+    for (name, _) in records.iter().flatten().flat_map(values) {
+        if !names.contains(name) {
+            names.push(*name);
+        }
+    }
+    names
+}
+
+/// Names of all metrics recorded for any of the treatments, in order of their first appearance.
+pub(crate) fn metric_names(records: &[Vec<TreatmentRecord>]) -> Vec<&'static str> {
+    names_of(records, |r| &r.metrics)
+}
+
+/// Names of all phases recorded for any of the treatments, in order of their first appearance.
+pub(crate) fn phase_names(records: &[Vec<TreatmentRecord>]) -> Vec<&'static str> {
+    names_of(records, |r| &r.phases)
+}
+
+/// Title of the summary column of the phase with the given `name`.
+pub(crate) fn phase_title(name: &str) -> String {
+    format!("{name} (ns)")
+}
+
+/// Returns whether or not statistics across replicates are recorded for any of the treatments.
+pub(crate) fn has_replicates(records: &[Vec<TreatmentRecord>]) -> bool {
+    records.iter().flatten().any(|r| r.replicates.is_some())
+}
+
+/// Returns whether or not allocations are recorded for any of the treatments.
+pub(crate) fn has_allocations(records: &[Vec<TreatmentRecord>]) -> bool {
+    records.iter().flatten().any(|r| r.allocations.is_some())
+}
+
+/// Returns whether or not sizes of the inputs are recorded for any of the treatments.
+pub(crate) fn has_input_sizes(records: &[Vec<TreatmentRecord>]) -> bool {
+    records
+        .iter()
+        .flatten()
+        .any(|r| r.input.is_some_and(|x| x.size_bytes.is_some()))
+}
+
+/// Titles of the input columns of the summary tables; the size column is included only if `has_sizes`.
+pub(crate) fn input_titles(has_sizes: bool) -> Vec<&'static str> {
+    match has_sizes {
+        true => vec!["Input Time (ms)", "Input Size (bytes)"],
+        false => vec!["Input Time (ms)"],
+    }
+}
+
+/// String representations of the input record to be used in the summary tables; the size column is
+/// included only if `has_sizes`.
+pub(crate) fn input_columns(input: Option<InputRecord>, has_sizes: bool) -> Vec<String> {
+    let time = input
+        .map(|x| format!("{:.3}", x.elapsed.as_secs_f64() * 1000.0))
+        .unwrap_or("NA".to_string());
+    match has_sizes {
+        true => {
+            let size = input
+                .and_then(|x| x.size_bytes)
+                .map(|x| x.to_string())
+                .unwrap_or("NA".to_string());
+            vec![time, size]
+        }
+        false => vec![time],
+    }
+}
+
+/// Titles of the allocation columns of the summary tables.
+pub(crate) const ALLOCATION_TITLES: [&str; 3] = ["Allocs", "Alloc (bytes)", "Peak (bytes)"];
+
+/// String representations of the allocation statistics to be used in the summary tables.
+pub(crate) fn allocation_columns(allocations: Option<AllocationStats>) -> [String; 3] {
+    match allocations {
+        Some(x) => [
+            x.count.to_string(),
+            x.bytes.to_string(),
+            x.peak_bytes.to_string(),
+        ],
+        None => ["NA".to_string(), "NA".to_string(), "NA".to_string()],
+    }
+}
+
+/// String representation of the time estimate of a treatment with the given `status` to be used in the summary tables.
+pub(crate) fn format_time(status: TreatmentStatus, estimate: Option<f64>) -> String {
+    match (status, estimate) {
+        (TreatmentStatus::TimedOut, _) => "TIMEOUT".to_string(),
+        (TreatmentStatus::Skipped, _) => "SKIPPED".to_string(),
+        (TreatmentStatus::NotRun, _) => "NOT RUN".to_string(),
+        (TreatmentStatus::Measured, Some(x)) => format!("{x:.0}"),
+        (TreatmentStatus::Measured, None) => "NA".to_string(),
+    }
+}
+
+/// String representation of the value of a metric to be used in the summary table.
+pub(crate) fn format_metric(value: Option<f64>) -> String {
+    match value {
+        Some(x) if x.fract().abs() < f64::EPSILON && x.abs() < 1e15 => format!("{x:.0}"),
+        Some(x) => format!("{x:.4}"),
+        None => "NA".to_string(),
+    }
+}
+
+/// Executes the treatment defined by `input_variant` and `alg_variant` once on the `input`, which is the
+/// `replicate`-th instance of the input level, validates its output and records the data of the treatment.
+///
+/// # Panics
+///
+/// Panics if the output fails validation by [`validate_output`], or does not match the [`expected_output`].
+///
+/// [`validate_output`]: crate::Experiment::validate_output
+/// [`expected_output`]: crate::Experiment::expected_output
+pub(crate) fn record_treatment<E: Experiment>(
+    exp: &mut E,
+    name: &str,
+    input_variant: &E::InputFactors,
+    replicate: usize,
+    alg_variant: &E::AlgFactors,
+    input: &E::Input,
+) -> TreatmentRecord {
+    let ((output, elapsed), allocations) = match exp.execution_mode() {
+        ExecutionMode::Borrowed => measure_allocations(|| {
+            let begin = Instant::now();
+            let output = exp.execute(alg_variant, input);
+            (output, begin.elapsed())
+        }),
+        ExecutionMode::Consumed { setup, .. } => {
+            let input = setup(input);
+            measure_allocations(|| {
+                let begin = Instant::now();
+                let output = exp.execute_mut(alg_variant, input);
+                (output, begin.elapsed())
+            })
+        }
+    };
+
+    exp.validate_output(input_variant, input, &output);
+
+    let expected_output = exp.expected_output(input_variant, input);
+    let validated = expected_output.is_some();
+    if let Some(expected_output) = expected_output {
+        let mismatch = exp
+            .outputs_match(&expected_output, &output)
+            .err()
+            .map(|message| {
+                let files = write_mismatch_files(
+                    exp,
+                    name,
+                    input_variant,
+                    replicate,
+                    alg_variant,
+                    &expected_output,
+                    &output,
+                )
+                .unwrap_or_else(|e| format!("Failed to write full outputs: {e}"));
+                format!("{message}\n{files}")
+            });
+        assert!(
+            mismatch.is_none(),
+            "Output of run does not match the expected output. Run: {}\n{}",
+            exp.instance_key_long(input_variant, replicate, alg_variant),
+            mismatch.unwrap_or_default(),
+        );
+    }
+
+    TreatmentRecord {
+        status: TreatmentStatus::Measured,
+        elapsed,
+        validated,
+        metrics: exp.metrics(input_variant, input, &output),
+        allocations,
+        phases: vec![],
+        replicates: None,
+        input: None,
+    }
+}
@@ -0,0 +1,61 @@
+/// Maximum length of a directory name created by criterion.
+const MAX_DIRECTORY_NAME_LEN: usize = 64;
+
+/// Defines how the treatments of an experiment are registered as criterion benchmarks.
+///
+/// It is determined by [`registration`] of the experiment.
+///
+/// [`registration`]: crate::Experiment::registration
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Registration {
+    /// All treatments are registered in a single benchmark group named after the experiment, where the
+    /// benchmark id of each treatment is its short key, such as "len:1024/alg:sort".
+    ///
+    /// This is the default registration.
+    #[default]
+    Flat,
+    /// Treatments are registered with criterion's `BenchmarkId::new(variant_key, input_param)`, where the
+    /// parameter is the level of the numeric `input_factor`, such as the length of the input.
+    ///
+    /// Treatments on the inputs sharing the levels of the remaining input factors, and the same replicate,
+    /// form a benchmark group named after the experiment and these levels, such as "search/pos:mid".
+    /// The algorithm variants are then the functions of the group, which allows criterion's html report to
+    /// draw the line chart of each group comparing the variants across the levels of the `input_factor`.
+    ///
+    /// Since criterion draws the chart from the benchmarks executed by the same group, the treatments are
+    /// executed group by group; within each group, the [`treatment_order`] is followed.
+    ///
+    /// [`treatment_order`]: crate::Experiment::treatment_order
+    Parameterized {
+        /// Name of the input factor, as returned by [`factor_names`], whose levels are the parameters of the
+        /// benchmarks; otherwise, the experiment fails with the [`Error::UnknownInputFactor`] error.
+        ///
+        /// [`factor_names`]: crate::Factors::factor_names
+        /// [`Error::UnknownInputFactor`]: crate::Error::UnknownInputFactor
+        input_factor: &'static str,
+    },
+}
+
+/// Replaces the characters of the `name` which are not allowed in file names by underscores.
+pub(crate) fn replace_special_chars(name: &str) -> String {
+    name.replace(
+        &['?', '"', '/', '\\', '*', '<', '>', ':', '|', '^'][..],
+        "_",
+    )
+}
+
+/// Converts the `name` into the directory name that criterion creates for it.
+pub(crate) fn filename_safe(name: &str) -> String {
+    let mut name = replace_special_chars(name);
+    if name.len() > MAX_DIRECTORY_NAME_LEN {
+        let mut len = MAX_DIRECTORY_NAME_LEN;
+        while !name.is_char_boundary(len) {
+            len -= 1;
+        }
+        name.truncate(len);
+    }
+    match cfg!(target_os = "windows") {
+        true => name.trim_end().to_lowercase(),
+        false => name,
+    }
+}
@@ -0,0 +1,211 @@
+use crate::random::SplitMix64;
+use crate::record::{InputRecord, TreatmentRecord, TreatmentStatus, metric_names, phase_names};
+use crate::{AllocationStats, ExperimentMeasurement};
+use std::time::Duration;
+
+/// A replicate, or instance, of an input level passed to [`input_replicate`].
+///
+/// When [`num_replicates`] is greater than one, several instances of each input level are created, such as
+/// random graphs or arrays with different seeds, and each of them is benchmarked with all algorithm variants.
+///
+/// [`input_replicate`]: crate::Experiment::input_replicate
+/// [`num_replicates`]: crate::Experiment::num_replicates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Replicate {
+    /// Index of the replicate, in `0..num_replicates`.
+    pub index: usize,
+    /// Seed of the replicate which can be used to generate a random instance of the input level.
+    pub seed: u64,
+}
+
+impl Replicate {
+    /// Creates the replicate with the given `index` of the input with the given `input_seed`.
+    ///
+    /// The first replicate uses the seed of the input, while the subsequent replicates use the numbers
+    /// generated from it.
+    pub(crate) fn new(input_seed: u64, index: usize) -> Self {
+        let mut rng = SplitMix64::new(input_seed);
+        let seed = (0..index).fold(input_seed, |_, _| rng.next_u64());
+        Self { index, seed }
+    }
+}
+
+/// Statistics of the time estimates of a treatment across the replicates of its input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ReplicateStats {
+    /// Number of the replicates with an estimate, which might be fewer than the replicates of the input.
+    pub count: usize,
+    /// Mean of the estimates.
+    pub mean: f64,
+    /// Minimum of the estimates.
+    pub min: f64,
+    /// Maximum of the estimates.
+    pub max: f64,
+}
+
+impl ReplicateStats {
+    /// Computes the statistics of the `values`; None if there is no value.
+    pub fn new(values: &[f64]) -> Option<Self> {
+        match values.is_empty() {
+            true => None,
+            false => Some(Self {
+                count: values.len(),
+                mean: values.iter().sum::<f64>() / values.len() as f64,
+                min: values.iter().copied().fold(f64::MAX, f64::min),
+                max: values.iter().copied().fold(f64::MIN, f64::max),
+            }),
+        }
+    }
+
+    /// Spread of the estimates as the range relative to the mean, in percent.
+    pub fn spread(&self) -> f64 {
+        match self.mean > 0.0 {
+            true => (self.max - self.min) / self.mean * 100.0,
+            false => 0.0,
+        }
+    }
+}
+
+/// Titles of the summary columns of the replicate statistics.
+pub(crate) fn replicate_titles<M: ExperimentMeasurement>() -> [String; 4] {
+    [
+        "Replicates".to_string(),
+        format!("Min ({})", M::unit()),
+        format!("Max ({})", M::unit()),
+        "Spread (%)".to_string(),
+    ]
+}
+
+/// String representations of the replicate statistics to be used in the summary tables.
+pub(crate) fn replicate_columns(stats: Option<ReplicateStats>) -> [String; 4] {
+    match stats {
+        Some(x) => [
+            x.count.to_string(),
+            format!("{:.0}", x.min),
+            format!("{:.0}", x.max),
+            format!("{:.2}", x.spread()),
+        ],
+        None => [
+            "NA".to_string(),
+            "NA".to_string(),
+            "NA".to_string(),
+            "NA".to_string(),
+        ],
+    }
+}
+
+/// Aggregates the `estimates` and `records` of the treatments on each instance of the inputs over the replicates.
+///
+/// Instances are ordered input-major, such that `estimates[i * num_replicates + r][a]` is the estimate of the
+/// algorithm variant `a` on the `r`-th replicate of the input `i`.
+/// The aggregated estimate is the mean over the replicates with an estimate, the number of which is recorded in
+/// the replicate statistics; and metrics, phases and allocations are averaged over the replicates.
+/// A treatment is considered as timed out if it timed out on any of the replicates, and skipped if it is
+/// skipped on any of the replicates.
+///
+/// Returns the estimates and records as they are when there exists a single replicate.
+pub(crate) fn aggregate_replicates(
+    num_replicates: usize,
+    estimates: Vec<Vec<Option<f64>>>,
+    records: Vec<Vec<TreatmentRecord>>,
+) -> (Vec<Vec<Option<f64>>>, Vec<Vec<TreatmentRecord>>) {
+    if num_replicates == 1 {
+        return (estimates, records);
+    }
+
+    let metric_names = metric_names(&records);
+    let phase_names = phase_names(&records);
+    let mean = |values: Vec<f64>| match values.is_empty() {
+        true => None,
+        false => Some(values.iter().sum::<f64>() / values.len() as f64),
+    };
+
+    let mut aggregated_estimates = vec![];
+    let mut aggregated_records = vec![];
+    for (input_estimates, input_records) in estimates
+        .chunks(num_replicates)
+        .zip(records.chunks(num_replicates))
+    {
+        let num_a = input_records.first().map(|x| x.len()).unwrap_or(0);
+        let mut alg_estimates = vec![];
+        let mut alg_records = vec![];
+        for a in 0..num_a {
+            let replicates: Vec<_> = input_records.iter().map(|x| &x[a]).collect();
+            let has_status = |status| replicates.iter().any(|x| x.status == status);
+            let status = match (
+                has_status(TreatmentStatus::TimedOut),
+                has_status(TreatmentStatus::Skipped),
+                has_status(TreatmentStatus::NotRun),
+            ) {
+                (true, _, _) => TreatmentStatus::TimedOut,
+                (false, true, _) => TreatmentStatus::Skipped,
+                (false, false, true) => TreatmentStatus::NotRun,
+                (false, false, false) => TreatmentStatus::Measured,
+            };
+
+            let values: Vec<_> = input_estimates.iter().filter_map(|x| x[a]).collect();
+            let stats = match status {
+                TreatmentStatus::Measured => ReplicateStats::new(&values),
+                _ => None,
+            };
+
+            let average = |value: &dyn Fn(&TreatmentRecord) -> Option<f64>| {
+                mean(replicates.iter().filter_map(|x| value(x)).collect())
+            };
+            let metrics = metric_names
+                .iter()
+                .filter_map(|m| average(&|x| x.metric(m)).map(|v| (*m, v)))
+                .collect();
+            let phases = phase_names
+                .iter()
+                .filter_map(|p| average(&|x| x.phase(p)).map(|v| (*p, v)))
+                .collect();
+            let allocations = replicates
+                .iter()
+                .map(|x| x.allocations)
+                .collect::<Option<Vec<_>>>()
+                .filter(|x| !x.is_empty())
+                .map(|x| {
+                    let n = x.len();
+                    AllocationStats {
+                        count: x.iter().map(|x| x.count).sum::<usize>() / n,
+                        bytes: x.iter().map(|x| x.bytes).sum::<usize>() / n,
+                        peak_bytes: x.iter().map(|x| x.peak_bytes).sum::<usize>() / n,
+                    }
+                });
+
+            let inputs: Vec<_> = replicates.iter().filter_map(|x| x.input).collect();
+            let input = match inputs.len() {
+                0 => None,
+                n => Some(InputRecord {
+                    elapsed: inputs.iter().map(|x| x.elapsed).sum::<Duration>() / n as u32,
+                    size_bytes: inputs
+                        .iter()
+                        .map(|x| x.size_bytes)
+                        .collect::<Option<Vec<_>>>()
+                        .map(|x| x.iter().sum::<usize>() / n),
+                }),
+            };
+
+            alg_estimates.push(stats.map(|x| x.mean));
+            alg_records.push(TreatmentRecord {
+                status,
+                elapsed: replicates
+                    .iter()
+                    .map(|x| x.elapsed)
+                    .max()
+                    .unwrap_or_default(),
+                validated: replicates.iter().all(|x| x.validated),
+                metrics,
+                allocations,
+                phases,
+                replicates: stats,
+                input,
+            });
+        }
+        aggregated_estimates.push(alg_estimates);
+        aggregated_records.push(alg_records);
+    }
+
+    (aggregated_estimates, aggregated_records)
+}
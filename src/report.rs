@@ -0,0 +1,219 @@
+use crate::experiment_sealed::ExperimentSealed;
+use crate::pivot::score_ranks;
+use crate::record::format_time;
+use crate::summary::{SummaryTable, create_file};
+use crate::{Experiment, ExperimentResult, Factors, TreatmentOrder, TreatmentResult};
+use std::io::Write;
+
+/// Escapes the `value` to be used as a cell of a markdown table.
+pub(crate) fn md_cell(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace(['\n', '\r'], " ")
+}
+
+/// Formats the `value` as inline code, enclosed in a backtick fence longer than any run of backticks in the
+/// value, and padded with spaces when the value starts or ends with a backtick.
+pub(crate) fn md_code(value: &str) -> String {
+    let longest_run = value
+        .split(|c| c != '`')
+        .map(|x| x.len())
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat(longest_run + 1);
+    match value.starts_with('`') || value.ends_with('`') {
+        true => format!("{fence} {value} {fence}"),
+        false => format!("{fence}{value}{fence}"),
+    }
+}
+
+/// Creates the lines of a markdown table with the given `titles` and `rows`, where the columns listed in
+/// `right_aligned` are aligned to the right.
+pub(crate) fn md_table<S: AsRef<str>>(
+    titles: &[S],
+    rows: &[Vec<String>],
+    right_aligned: impl Fn(usize) -> bool,
+) -> String {
+    let line = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+    let mut table = line(titles.iter().map(|x| md_cell(x.as_ref())).collect());
+    table.push_str(&line(
+        (0..titles.len())
+            .map(|j| match right_aligned(j) {
+                true => "---:".to_string(),
+                false => "---".to_string(),
+            })
+            .collect(),
+    ));
+    for row in rows {
+        table.push_str(&line(row.iter().map(|x| md_cell(x)).collect()));
+    }
+    table
+}
+
+/// Markdown table of the factors of type `F` with their short names and levels in order of their first
+/// appearance in `variants`.
+fn factors_table<F: Factors>(variants: &[F]) -> String {
+    let names = F::factor_names();
+    let names_short = F::factor_names_short();
+    let rows: Vec<_> = names
+        .iter()
+        .zip(&names_short)
+        .enumerate()
+        .map(|(j, (name, name_short))| {
+            let mut levels: Vec<String> = vec![];
+            for variant in variants {
+                let (level, level_short) = (
+                    &variant.factor_levels()[j],
+                    &variant.factor_levels_short()[j],
+                );
+                let level = match level == level_short {
+                    true => md_code(level),
+                    false => format!("{} ({})", md_code(level), md_code(level_short)),
+                };
+                if !levels.contains(&level) {
+                    levels.push(level);
+                }
+            }
+            vec![name.to_string(), name_short.to_string(), levels.join(", ")]
+        })
+        .collect();
+    md_table(&["Factor", "Short Name", "Levels"], &rows, |_| false)
+}
+
+/// Markdown table of the best and worst algorithm variants on each input.
+pub(crate) fn best_worst_table<E: Experiment>(result: &ExperimentResult<'_, E>) -> String {
+    let unit = result.time_unit;
+    let rows: Vec<_> = result
+        .input_levels
+        .iter()
+        .enumerate()
+        .map(|(i, input)| {
+            let treatments = result.treatments_for(input);
+            let ranked = treatments.iter().filter(|x| x.rank.is_some());
+            let best = ranked.clone().min_by_key(|x| x.rank);
+            // the worst variant is missing when at most one variant is measured
+            let worst = ranked.max_by_key(|x| x.rank).filter(|x| x.rank > Some(1));
+            let variant = |x: Option<&TreatmentResult<'_, E>>| match x {
+                Some(x) => [
+                    format!("[a = {}] {}", x.a, x.alg.key_long()),
+                    format_time(x.status, x.estimate),
+                ],
+                None => ["NA".to_string(), "NA".to_string()],
+            };
+            let mut row = vec![(i + 1).to_string(), input.key_long()];
+            row.extend(variant(best));
+            row.extend(variant(worst));
+            row
+        })
+        .collect();
+    let titles = [
+        "i".to_string(),
+        "Input".to_string(),
+        "Best".to_string(),
+        format!("Best ({unit})"),
+        "Worst".to_string(),
+        format!("Worst ({unit})"),
+    ];
+    md_table(&titles, &rows, |j| j == 3 || j == 5)
+}
+
+/// Overall recommendation followed by the ranking of the algorithm variants measured on all inputs.
+pub(crate) fn recommendation<E: Experiment>(result: &ExperimentResult<'_, E>) -> String {
+    let scores = result.relative_scores();
+    let ranks = score_ranks(&scores);
+    let mut ranked: Vec<_> = ranks
+        .iter()
+        .enumerate()
+        .filter_map(|(a, rank)| rank.map(|rank| (rank, a)))
+        .collect();
+    ranked.sort();
+
+    let Some(&(_, best)) = ranked.first() else {
+        return "None of the algorithm variants is measured on all inputs; hence, no variant is recommended.\n"
+            .to_string();
+    };
+
+    let mut text = format!(
+        "**[a = {}] {}** is recommended as the best algorithm variant across all inputs.\n\n",
+        best + 1,
+        result.alg_levels[best].key_long()
+    );
+    text.push_str(
+        "Variants measured on all inputs are ranked by the geometric mean of their time relative to the fastest variant of each input:\n\n",
+    );
+    let rows: Vec<_> = ranked
+        .iter()
+        .map(|&(rank, a)| {
+            vec![
+                rank.to_string(),
+                (a + 1).to_string(),
+                result.alg_levels[a].key_long(),
+                scores[a]
+                    .map(|x| format!("{x:.2}"))
+                    .unwrap_or("NA".to_string()),
+            ]
+        })
+        .collect();
+    text.push_str(&md_table(
+        &["Rank", "a", "Variant", "Relative (geo mean)"],
+        &rows,
+        |j| j == 3,
+    ));
+    text
+}
+
+/// Creates the "report_{name}.md" file presenting the experiment and its results in markdown.
+pub(crate) fn create_markdown_report<E: Experiment>(
+    exp: &E,
+    result: &ExperimentResult<'_, E>,
+    table: &SummaryTable,
+) -> std::io::Result<()> {
+    let name = &result.name;
+    let path = exp.report_path(name);
+    let mut file = create_file(&path)?;
+
+    let num_i = result.input_levels.len();
+    let num_a = result.alg_levels.len();
+    let description = exp.description().unwrap_or(format!(
+        "Factorial experiment of the '{name}' benchmark evaluating {num_a} algorithm variants on {num_i} inputs."
+    ));
+    let order = match exp.treatment_order() {
+        TreatmentOrder::Canonical => "canonical".to_string(),
+        TreatmentOrder::Randomized { seed } => format!("randomized with seed {seed}"),
+        TreatmentOrder::BlockedRandomized { seed } => {
+            format!("blocked randomized with seed {seed}")
+        }
+    };
+
+    let mut report = format!("# {name}\n\n{description}\n\n");
+    report.push_str(&format!(
+        "- **Treatments:** {} ({num_i} inputs x {num_a} algorithm variants)\n",
+        num_i * num_a
+    ));
+    report.push_str(&format!(
+        "- **Replicates per input:** {}\n",
+        exp.num_replicates()
+    ));
+    report.push_str(&format!("- **Master seed:** {}\n", exp.master_seed()));
+    report.push_str(&format!("- **Treatment order:** {order}\n"));
+    report.push_str(&format!("- **Time unit:** {}\n\n", result.time_unit));
+
+    report.push_str("## Input Factors\n\n");
+    report.push_str(&factors_table(result.input_levels));
+    report.push_str("\n## Algorithm Factors\n\n");
+    report.push_str(&factors_table(result.alg_levels));
+
+    report.push_str("\n## Summary\n\n");
+    report.push_str(&md_table(&table.titles, &table.rows, |j| {
+        j >= table.num_ids
+    }));
+
+    report.push_str("\n## Best and Worst Variants per Input\n\n");
+    report.push_str(&best_worst_table(result));
+
+    report.push_str("\n## Recommendation\n\n");
+    report.push_str(&recommendation(result));
+
+    file.write_all(report.as_bytes())
+}
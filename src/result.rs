@@ -0,0 +1,219 @@
+use crate::record::{TreatmentRecord, TreatmentStatus};
+use crate::{Experiment, Factors};
+use std::cmp::Ordering;
+
+/// Status of the validation of the output of a treatment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Validation {
+    /// The output matched the output returned by [`expected_output`].
+    ///
+    /// [`expected_output`]: crate::Experiment::expected_output
+    Matched,
+    /// There exists no expected output for the input, and hence, the output is only checked by
+    /// [`validate_output`].
+    ///
+    /// [`validate_output`]: crate::Experiment::validate_output
+    Unchecked,
+    /// The treatment is not executed, and hence, its output is not validated.
+    NotExecuted,
+}
+
+/// Result of a single treatment of the experiment, which is an element of the [`ExperimentResult`].
+pub struct TreatmentResult<'a, E: Experiment> {
+    /// Index of the treatment, starting from 1.
+    pub t: usize,
+    /// Index of the input of the treatment, starting from 1.
+    pub i: usize,
+    /// Index of the algorithm variant of the treatment, starting from 1.
+    pub a: usize,
+    /// Input factor levels of the treatment.
+    pub input: &'a E::InputFactors,
+    /// Algorithm factor levels of the treatment.
+    pub alg: &'a E::AlgFactors,
+    /// Status of the treatment.
+    pub status: TreatmentStatus,
+    /// Point estimate of the time of the treatment in the unit of the measurement; None if the treatment is
+    /// not measured.
+    ///
+    /// When the inputs are replicated, this is the mean of the estimates across the replicates.
+    pub estimate: Option<f64>,
+    /// Status of the validation of the output of the treatment.
+    pub validation: Validation,
+    /// Rank of the treatment among the algorithm variants on the same input, where the fastest variant has
+    /// rank 1; None if the treatment is not measured.
+    pub rank: Option<usize>,
+    /// Secondary responses of the treatment computed by [`metrics`].
+    ///
+    /// [`metrics`]: crate::Experiment::metrics
+    pub metrics: Vec<(&'static str, f64)>,
+}
+
+/// Result of an experiment returned by [`bench`], which allows to post-process the results programmatically.
+///
+/// It holds references to the input and algorithm levels of the experiment together with the result of each
+/// treatment.
+/// Further, it ranks the algorithm variants for each input by their time estimates, and overall across all
+/// inputs.
+///
+/// The overall ranking considers only the variants measured on all inputs.
+/// These variants are ranked by the geometric mean of their time relative to the fastest variant of each
+/// input, so that each input has the same weight regardless of its size.
+///
+/// [`bench`]: crate::Experiment::bench
+pub struct ExperimentResult<'a, E: Experiment> {
+    /// Name of the benchmark.
+    pub name: String,
+    /// Unit of the time estimates, such as "ns".
+    pub time_unit: &'static str,
+    /// Input levels of the experiment.
+    pub input_levels: &'a [E::InputFactors],
+    /// Algorithm levels of the experiment.
+    pub alg_levels: &'a [E::AlgFactors],
+    treatments: Vec<TreatmentResult<'a, E>>,
+}
+
+impl<'a, E: Experiment> ExperimentResult<'a, E> {
+    /// Creates the result from the aggregated estimates and records of the treatments.
+    pub(crate) fn new(
+        name: &str,
+        time_unit: &'static str,
+        input_levels: &'a [E::InputFactors],
+        alg_levels: &'a [E::AlgFactors],
+        estimates: &[Vec<Option<f64>>],
+        records: &[Vec<TreatmentRecord>],
+    ) -> Self {
+        let num_a = alg_levels.len();
+        let mut treatments = vec![];
+        for (i, (input_estimates, input_records)) in estimates.iter().zip(records).enumerate() {
+            let ranks = ranks(input_estimates);
+            for (a, (estimate, record)) in input_estimates.iter().zip(input_records).enumerate() {
+                let validation = match (record.status, record.validated) {
+                    (TreatmentStatus::Skipped | TreatmentStatus::NotRun, _) => {
+                        Validation::NotExecuted
+                    }
+                    (_, true) => Validation::Matched,
+                    (_, false) => Validation::Unchecked,
+                };
+                treatments.push(TreatmentResult {
+                    t: i * num_a + a + 1,
+                    i: i + 1,
+                    a: a + 1,
+                    input: &input_levels[i],
+                    alg: &alg_levels[a],
+                    status: record.status,
+                    estimate: *estimate,
+                    validation,
+                    rank: ranks[a],
+                    metrics: record.metrics.clone(),
+                });
+            }
+        }
+
+        Self {
+            name: name.to_string(),
+            time_unit,
+            input_levels,
+            alg_levels,
+            treatments,
+        }
+    }
+
+    /// Results of all treatments in the canonical order, where the algorithm variants are iterated in the
+    /// inner loop.
+    pub fn treatments(&self) -> &[TreatmentResult<'a, E>] {
+        &self.treatments
+    }
+
+    /// Results of the treatments on the input with the given `input` levels; empty if the input does not
+    /// belong to the experiment.
+    ///
+    /// Inputs are identified by their [`key_long`].
+    ///
+    /// [`key_long`]: crate::Factors::key_long
+    pub fn treatments_for(&self, input: &E::InputFactors) -> &[TreatmentResult<'a, E>] {
+        let key = input.key_long();
+        match self.input_levels.iter().position(|x| x.key_long() == key) {
+            Some(i) => {
+                let num_a = self.alg_levels.len();
+                &self.treatments[i * num_a..(i + 1) * num_a]
+            }
+            None => &[],
+        }
+    }
+
+    /// Algorithm variants measured on the given `input` sorted from the fastest to the slowest.
+    pub fn ranking_for(&self, input: &E::InputFactors) -> Vec<&'a E::AlgFactors> {
+        let mut measured: Vec<_> = self
+            .treatments_for(input)
+            .iter()
+            .filter_map(|x| x.rank.map(|rank| (rank, x.alg)))
+            .collect();
+        measured.sort_by_key(|x| x.0);
+        measured.into_iter().map(|x| x.1).collect()
+    }
+
+    /// The fastest algorithm variant on the given `input`; None if none of the variants is measured on it.
+    pub fn best_for(&self, input: &E::InputFactors) -> Option<&'a E::AlgFactors> {
+        self.ranking_for(input).first().copied()
+    }
+
+    /// Algorithm variants measured on all inputs sorted from the best to the worst by the geometric mean of
+    /// their time relative to the fastest variant of each input.
+    pub fn overall_ranking(&self) -> Vec<&'a E::AlgFactors> {
+        let mut scores: Vec<_> = self
+            .relative_scores()
+            .into_iter()
+            .enumerate()
+            .filter_map(|(a, score)| score.map(|x| (x, &self.alg_levels[a])))
+            .collect();
+        scores.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap_or(Ordering::Equal));
+        scores.into_iter().map(|x| x.1).collect()
+    }
+
+    /// The best algorithm variant across all inputs; see [`overall_ranking`] for the criterion.
+    ///
+    /// [`overall_ranking`]: ExperimentResult::overall_ranking
+    pub fn best_overall(&self) -> Option<&'a E::AlgFactors> {
+        self.overall_ranking().first().copied()
+    }
+
+    /// Geometric mean of the time of each algorithm variant relative to the fastest variant of each input;
+    /// None for the variants which are not measured on all inputs.
+    pub(crate) fn relative_scores(&self) -> Vec<Option<f64>> {
+        let num_a = self.alg_levels.len();
+        let mut log_sums = vec![Some(0.0); num_a];
+        for input_treatments in self.treatments.chunks(num_a.max(1)) {
+            let best = input_treatments
+                .iter()
+                .filter_map(|x| x.estimate)
+                .min_by(|x, y| x.partial_cmp(y).unwrap_or(Ordering::Equal));
+            for (sum, treatment) in log_sums.iter_mut().zip(input_treatments) {
+                *sum = match (*sum, treatment.estimate, best) {
+                    (Some(sum), Some(x), Some(best)) if best > 0.0 => Some(sum + (x / best).ln()),
+                    (Some(sum), Some(_), Some(_)) => Some(sum),
+                    _ => None,
+                };
+            }
+        }
+        let num_i = self.input_levels.len().max(1) as f64;
+        log_sums
+            .into_iter()
+            .map(|x| x.map(|sum| (sum / num_i).exp()))
+            .collect()
+    }
+}
+
+/// Ranks of the `estimates`, starting from 1 for the smallest estimate; None for the missing estimates.
+fn ranks(estimates: &[Option<f64>]) -> Vec<Option<usize>> {
+    let mut measured: Vec<_> = estimates
+        .iter()
+        .enumerate()
+        .filter_map(|(a, x)| x.map(|x| (a, x)))
+        .collect();
+    measured.sort_by(|x, y| x.1.partial_cmp(&y.1).unwrap_or(Ordering::Equal));
+    let mut ranks = vec![None; estimates.len()];
+    for (rank, (a, _)) in measured.into_iter().enumerate() {
+        ranks[a] = Some(rank + 1);
+    }
+    ranks
+}
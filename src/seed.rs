@@ -0,0 +1,39 @@
+use crate::random::SplitMix64;
+
+/// Offset basis of the 64-bit FNV-1a hash.
+const FNV_OFFSET_BASIS: u64 = 0xCBF2_9CE4_8422_2325;
+
+/// Prime of the 64-bit FNV-1a hash.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01B3;
+
+/// Derives a seed from the `master_seed` of an experiment and the `key` of an input, such as its
+/// [`key_long`].
+///
+/// The seed is a stable hash of the key mixed with the master seed; it does not depend on the platform,
+/// the compiler version or the execution order of the treatments.
+/// Therefore, the same input is created identically in every run of the experiment as long as the
+/// master seed is unchanged, while different inputs get different seeds.
+///
+/// It is used by [`input_seed`] which is the convenient way to seed the random input generation in
+/// [`input`].
+///
+/// [`key_long`]: crate::Factors::key_long
+/// [`input_seed`]: crate::Experiment::input_seed
+/// [`input`]: crate::Experiment::input
+///
+/// # Examples
+///
+/// ```
+/// use orx_criterion::derive_seed;
+///
+/// let seed = derive_seed(42, "len:1024_density:0.5");
+/// assert_eq!(seed, derive_seed(42, "len:1024_density:0.5"));
+/// assert_ne!(seed, derive_seed(42, "len:2048_density:0.5"));
+/// assert_ne!(seed, derive_seed(7, "len:1024_density:0.5"));
+/// ```
+pub fn derive_seed(master_seed: u64, key: &str) -> u64 {
+    let hash = key.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    });
+    SplitMix64::new(hash ^ master_seed).next_u64()
+}
@@ -1,69 +1,110 @@
+use crate::estimate::{Estimate, aggregate_estimates};
 use crate::experiment_sealed::ExperimentSealed;
-use crate::{Experiment, Factors};
+use crate::measurement::time_title;
+use crate::pareto::ParetoFronts;
+use crate::pivot::{create_pivot_csv, print_pivot_table};
+use crate::record::{
+    ALLOCATION_TITLES, TreatmentRecord, TreatmentStatus, allocation_columns, format_metric,
+    format_time, has_allocations, has_input_sizes, has_replicates, input_columns, input_titles,
+    metric_names, phase_names, phase_title,
+};
+use crate::replicate::{aggregate_replicates, replicate_columns, replicate_titles};
+use crate::report::create_markdown_report;
+use crate::summary_json::create_summary_json;
+use crate::throughput::{format_rate, processed_kinds};
+use crate::{Error, Experiment, ExperimentMeasurement, ExperimentResult, Factors};
 use cli_table::{Cell, CellStruct, Color, Style, Table, format::Justify, print_stdout};
 use colorize::AnsiColor;
-use std::fs::File;
-use std::io::{Read, Write};
-use std::{cmp::Ordering, path::PathBuf};
-
-fn collect_point_estimates<E: Experiment>(
+use criterion::Throughput;
+use std::cmp::Ordering;
+use std::fs::{File, create_dir_all};
+use std::io::Write;
+use std::path::Path;
+
+/// Collects the estimates of the treatments on each instance of the inputs; estimates of the treatments
+/// which are not measured are missing, even if estimates of a previous run exist.
+fn collect_estimates<E: Experiment>(
     exp: &E,
     name: &str,
     input_levels: &[E::InputFactors],
     alg_levels: &[E::AlgFactors],
-) -> Vec<Vec<Option<f64>>> {
-    input_levels
+    records: &[Vec<TreatmentRecord>],
+) -> Vec<Vec<Option<Estimate>>> {
+    let num_r = exp.num_replicates();
+    records
         .iter()
-        .map(|input_variant| {
+        .enumerate()
+        .map(|(k, input_records)| {
+            let input_variant = &input_levels[k / num_r];
             alg_levels
                 .iter()
-                .map(|alg_variant| {
-                    let execution_path = exp.run_estimates_path(name, input_variant, alg_variant);
-                    get_slope_point_estimate(&execution_path)
+                .zip(input_records)
+                .map(|(alg_variant, record)| match record.status {
+                    TreatmentStatus::Measured => {
+                        let execution_path =
+                            exp.run_estimates_path(name, input_variant, k % num_r, alg_variant);
+                        Estimate::read(&execution_path)
+                    }
+                    TreatmentStatus::TimedOut
+                    | TreatmentStatus::Skipped
+                    | TreatmentStatus::NotRun => None,
                 })
                 .collect()
         })
         .collect()
 }
 
-fn get_slope_point_estimate(path: &PathBuf) -> Option<f64> {
-    let mut file = File::open(path).ok()?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).ok()?;
-
-    let field_slope_null = "\"slope\":null";
-    let is_slope_null = contents.contains(field_slope_null);
-
-    let field = match is_slope_null {
-        true => "\"mean\"",
-        false => "\"slope\"",
-    };
-    let position = contents.find(field)?;
-    let begin = position + field.len();
-    let slice = &contents[begin..];
-
-    let field_estimate = "\"point_estimate\":";
-    let position = slice.find(field_estimate)?;
-    let begin = position + field_estimate.len();
-    let slice = &slice[begin..];
-
-    let comma = ",";
-    let position = slice.find(comma)?;
-    let slice = &slice[0..position];
-
-    slice.parse().ok()
+/// Creates the file at `path` together with its missing parent directories.
+pub(crate) fn create_file(path: &Path) -> std::io::Result<File> {
+    if let Some(dir) = path.parent() {
+        create_dir_all(dir)?;
+    }
+    File::create(path)
 }
 
-pub fn summarize<E: Experiment>(
+/// Summarizes the experiment by writing the summary files and printing the summary table, and returns the
+/// result of the experiment.
+pub(crate) fn try_summarize<'a, E: Experiment, M: ExperimentMeasurement>(
     exp: &E,
     name: &str,
-    input_levels: &[E::InputFactors],
-    alg_levels: &[E::AlgFactors],
-) {
-    let estimates = collect_point_estimates(exp, name, input_levels, alg_levels);
+    input_levels: &'a [E::InputFactors],
+    alg_levels: &'a [E::AlgFactors],
+    instance_records: &[Vec<TreatmentRecord>],
+) -> Result<ExperimentResult<'a, E>, Error> {
+    let instance_intervals =
+        collect_estimates(exp, name, input_levels, alg_levels, instance_records);
+    let instance_estimates: Vec<Vec<_>> = instance_intervals
+        .iter()
+        .map(|x| x.iter().map(|x| x.map(|x| x.point)).collect())
+        .collect();
+
+    if exp.num_replicates() > 1 {
+        create_replicates_csv::<E, M>(
+            exp,
+            name,
+            input_levels,
+            alg_levels,
+            &instance_estimates,
+            instance_records,
+        )
+        .map_err(Error::write(exp.replicates_csv_path(name)))?;
+        let log = format!(
+            "\nTimes of the treatments on each replicate of the inputs are listed at:\n{:?}\n",
+            exp.replicates_csv_path(name)
+        );
+        println!("{}", log.italic());
+    }
 
-    create_summary_csv(exp, name, input_levels, alg_levels, &estimates)
-        .expect("Failed to create csv summary");
+    let (estimates, records) = aggregate_replicates(
+        exp.num_replicates(),
+        instance_estimates,
+        instance_records.to_vec(),
+    );
+    let records = &records;
+    let throughputs: Vec<_> = input_levels.iter().map(|x| exp.throughput(x)).collect();
+
+    let table = summary_table::<E, M>(input_levels, alg_levels, &estimates, &throughputs, records);
+    create_summary_csv(exp, name, &table).map_err(Error::write(exp.summary_csv_path(name)))?;
 
     let log = format!(
         "\nSummary table created at:\n{:?}\n",
@@ -71,39 +112,167 @@ pub fn summarize<E: Experiment>(
     );
     println!("{}", log.italic());
 
-    print_summary_table::<E>(name, input_levels, alg_levels, &estimates);
+    if exp.csv_format().long_format {
+        create_summary_long_csv(exp, name, &table)
+            .map_err(Error::write(exp.summary_long_csv_path(name)))?;
+        let log = format!(
+            "\nSummary table in the long format created at:\n{:?}\n",
+            exp.summary_long_csv_path(name)
+        );
+        println!("{}", log.italic());
+    }
+
+    let metric_names = metric_names(records);
+    let pareto = match metric_names.is_empty() {
+        true => None,
+        false => Some(ParetoFronts::new(exp, &metric_names, &estimates, records)),
+    };
+
+    if let Some(pareto) = &pareto {
+        create_pareto_csv::<E, M>(
+            exp,
+            name,
+            input_levels,
+            alg_levels,
+            &estimates,
+            records,
+            pareto,
+        )
+        .map_err(Error::write(exp.pareto_csv_path(name)))?;
+        let log = format!(
+            "\nPareto-optimal variants over time and metrics are listed at:\n{:?}\n",
+            exp.pareto_csv_path(name)
+        );
+        println!("{}", log.italic());
+    }
+
+    let result = ExperimentResult::new(
+        name,
+        M::unit(),
+        input_levels,
+        alg_levels,
+        &estimates,
+        records,
+    );
+
+    create_pivot_csv(exp, &result).map_err(Error::write(exp.pivot_csv_path(name)))?;
+    let log = format!(
+        "\nPivot table of the times with inputs as rows and variants as columns created at:\n{:?}\n",
+        exp.pivot_csv_path(name)
+    );
+    println!("{}", log.italic());
+
+    create_markdown_report(exp, &result, &table).map_err(Error::write(exp.report_path(name)))?;
+    let log = format!(
+        "\nMarkdown report of the experiment created at:\n{:?}\n",
+        exp.report_path(name)
+    );
+    println!("{}", log.italic());
+
+    let layout = exp.summary_layout();
+    if layout.has_table() {
+        print_summary_table::<E, M>(
+            name,
+            input_levels,
+            alg_levels,
+            &estimates,
+            &throughputs,
+            records,
+            pareto.as_ref(),
+        )
+        .map_err(Error::Print)?;
+    }
+    if layout.has_pivot() {
+        print_pivot_table::<E, M>(&result).map_err(Error::Print)?;
+    }
+
+    if let Some(pareto) = &pareto {
+        let log = "\nPareto-optimal variants across all inputs:";
+        println!("{}", log.bold());
+        for (a, alg_variant) in alg_levels.iter().enumerate() {
+            if pareto.overall[a] {
+                println!("* [a = {}]: {}", a + 1, alg_variant.key_long());
+            }
+        }
+    }
 
-    create_ai_prompt_to_analyze(exp, name, input_levels, alg_levels)
-        .expect("Failed to create ai prompt");
+    create_ai_prompt_to_analyze::<E, M>(exp, name, input_levels, alg_levels, records)
+        .map_err(Error::write(exp.ai_prompt_path(name)))?;
     let log = format!(
         "\nA draft AI prompt to analyze the summary table is created at:\n{:?}\n",
         exp.ai_prompt_path(name)
     );
     println!("{}", log.italic());
+
+    let intervals = aggregate_estimates(exp.num_replicates(), &instance_intervals);
+    create_summary_json(exp, &result, &intervals)
+        .map_err(Error::write(exp.summary_json_path(name)))?;
+    let log = format!(
+        "\nJson summary created at:\n{:?}\n",
+        exp.summary_json_path(name)
+    );
+    println!("{}", log.italic());
+
+    Ok(result)
 }
 
-fn create_summary_csv<E: Experiment>(
-    exp: &E,
-    name: &str,
+/// Summary table in the wide format, where each row is a treatment; the first `num_ids` columns identify the
+/// treatment by its indices and factor levels.
+pub(crate) struct SummaryTable {
+    pub num_ids: usize,
+    pub titles: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+fn summary_table<E: Experiment, M: ExperimentMeasurement>(
     input_levels: &[E::InputFactors],
     alg_levels: &[E::AlgFactors],
     estimates: &[Vec<Option<f64>>],
-) -> std::io::Result<()> {
-    let path = exp.summary_csv_path(name);
-    let mut file = File::create(path)?;
+    throughputs: &[Option<Throughput>],
+    records: &[Vec<TreatmentRecord>],
+) -> SummaryTable {
+    let time_title = time_title::<M>();
+    let metric_names = metric_names(records);
+    let has_allocations = has_allocations(records);
+    let phase_names = phase_names(records);
+    let phase_titles: Vec<_> = phase_names.iter().map(|x| phase_title(x)).collect();
+    let processed = processed_kinds(throughputs);
+    let processed_titles: Vec<_> = processed.iter().map(|x| x.title::<M>()).collect();
+    let has_replicates = has_replicates(records);
+    let replicate_titles = replicate_titles::<M>();
+    let has_input_sizes = has_input_sizes(records);
 
     // title
     let mut row = vec!["t", "i", "a"];
     row.extend_from_slice(&<E::InputFactors as Factors>::factor_names());
     row.extend_from_slice(&<E::AlgFactors as Factors>::factor_names());
-    row.push("Time (ns)");
-    file.write_all(row.join(",").as_bytes())?;
-    file.write_all(b"\n")?;
+    let num_ids = row.len();
+    row.push(&time_title);
+    if has_replicates {
+        row.extend(replicate_titles.iter().map(|x| x.as_str()));
+    }
+    row.extend(processed_titles.iter().map(|x| x.as_str()));
+    row.extend(phase_titles.iter().map(|x| x.as_str()));
+    row.extend_from_slice(&metric_names);
+    if has_allocations {
+        row.extend_from_slice(&ALLOCATION_TITLES);
+    }
+    row.extend(input_titles(has_input_sizes));
+    let titles = row.into_iter().map(|x| x.to_string()).collect();
 
     // rows
-    for (i, (input_variant, input_estimates)) in input_levels.iter().zip(estimates).enumerate() {
+    let mut rows = vec![];
+    for (i, ((input_variant, input_estimates), input_records)) in
+        input_levels.iter().zip(estimates).zip(records).enumerate()
+    {
+        let throughput = throughputs[i].as_ref();
         let input_factor_levels = input_variant.factor_levels();
-        for (a, (alg_variant, estimate)) in alg_levels.iter().zip(input_estimates).enumerate() {
+        for (a, ((alg_variant, estimate), record)) in alg_levels
+            .iter()
+            .zip(input_estimates)
+            .zip(input_records)
+            .enumerate()
+        {
             let t = i * alg_levels.len() + a;
             let alg_factor_levels = alg_variant.factor_levels();
             let mut row = vec![
@@ -113,23 +282,116 @@ fn create_summary_csv<E: Experiment>(
             ];
             row.extend(input_factor_levels.iter().map(|x| x.to_string()));
             row.extend_from_slice(&alg_factor_levels);
-            let estimate = estimate
-                .map(|x| format!("{x:.0}"))
-                .unwrap_or("NA".to_string());
-            row.push(estimate);
-            file.write_all(row.join(",").as_bytes())?;
-            file.write_all(b"\n")?;
+            row.push(format_time(record.status, *estimate));
+            if has_replicates {
+                row.extend(replicate_columns(record.replicates));
+            }
+            for kind in &processed {
+                let value = kind.rate::<M>(throughput, *estimate);
+                row.push(value.map(|x| format!("{x:.0}")).unwrap_or("NA".to_string()));
+            }
+            for phase in &phase_names {
+                let value = record.phase(phase);
+                row.push(value.map(|x| format!("{x:.0}")).unwrap_or("NA".to_string()));
+            }
+            for metric in &metric_names {
+                let value = record.metric(metric);
+                row.push(value.map(|x| x.to_string()).unwrap_or("NA".to_string()));
+            }
+            if has_allocations {
+                row.extend(allocation_columns(record.allocations));
+            }
+            row.extend(input_columns(record.input, has_input_sizes));
+            rows.push(row);
+        }
+    }
+
+    SummaryTable {
+        num_ids,
+        titles,
+        rows,
+    }
+}
+
+/// Title of the column of the summary csv files holding the master seed of the experiment.
+const MASTER_SEED_TITLE: &str = "master_seed";
+
+/// Inserts the `master_seed` of the experiment to the cells of a row of the summary csv, right after the
+/// identifiers of the treatment.
+fn with_master_seed(cells: &[String], num_ids: usize, master_seed: String) -> Vec<String> {
+    let mut row = cells[..num_ids].to_vec();
+    row.push(master_seed);
+    row.extend_from_slice(&cells[num_ids..]);
+    row
+}
+
+fn create_summary_csv<E: Experiment>(
+    exp: &E,
+    name: &str,
+    table: &SummaryTable,
+) -> std::io::Result<()> {
+    let format = exp.csv_format();
+    let path = exp.summary_csv_path(name);
+    let mut file = create_file(&path)?;
+    let master_seed = exp.master_seed().to_string();
+
+    let titles = with_master_seed(&table.titles, table.num_ids, MASTER_SEED_TITLE.to_string());
+    file.write_all(format.row(&titles).as_bytes())?;
+    for row in &table.rows {
+        let row = with_master_seed(row, table.num_ids, master_seed.clone());
+        file.write_all(format.row(&row).as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Creates the summary csv in the long format, where each row holds a single statistic of a treatment.
+fn create_summary_long_csv<E: Experiment>(
+    exp: &E,
+    name: &str,
+    table: &SummaryTable,
+) -> std::io::Result<()> {
+    let format = exp.csv_format();
+    let path = exp.summary_long_csv_path(name);
+    let mut file = create_file(&path)?;
+
+    let master_seed = exp.master_seed().to_string();
+
+    let (id_titles, statistics) = table.titles.split_at(table.num_ids);
+    let mut titles = id_titles.to_vec();
+    titles.extend([
+        MASTER_SEED_TITLE.to_string(),
+        "statistic".to_string(),
+        "value".to_string(),
+    ]);
+    file.write_all(format.row(&titles).as_bytes())?;
+
+    for row in &table.rows {
+        let (ids, values) = row.split_at(table.num_ids);
+        for (statistic, value) in statistics.iter().zip(values) {
+            let mut long_row = ids.to_vec();
+            long_row.extend([master_seed.clone(), statistic.clone(), value.clone()]);
+            file.write_all(format.row(&long_row).as_bytes())?;
         }
     }
     Ok(())
 }
 
-fn print_summary_table<E: Experiment>(
+fn print_summary_table<E: Experiment, M: ExperimentMeasurement>(
     name: &str,
     input_levels: &[E::InputFactors],
     alg_levels: &[E::AlgFactors],
     estimates: &[Vec<Option<f64>>],
-) {
+    throughputs: &[Option<Throughput>],
+    records: &[Vec<TreatmentRecord>],
+    pareto: Option<&ParetoFronts>,
+) -> std::io::Result<()> {
+    let time_title = time_title::<M>();
+    let metric_names = metric_names(records);
+    let has_allocations = has_allocations(records);
+    let phase_names = phase_names(records);
+    let processed = processed_kinds(throughputs);
+    let has_replicates = has_replicates(records);
+    let has_input_sizes = has_input_sizes(records);
     let cmp = |a: &f64, b: &f64| match a < b {
         true => Ordering::Less,
         false => Ordering::Greater,
@@ -153,11 +415,39 @@ fn print_summary_table<E: Experiment>(
     for param in <E::AlgFactors as Factors>::factor_names() {
         title.push(param.cell().bold(true));
     }
-    title.push("Time (ns)".cell().bold(true).justify(Justify::Right));
+    title.push(time_title.cell().bold(true).justify(Justify::Right));
+    if has_replicates {
+        for x in replicate_titles::<M>() {
+            title.push(x.cell().bold(true).justify(Justify::Right));
+        }
+    }
+    for kind in &processed {
+        title.push(kind.title::<M>().cell().bold(true).justify(Justify::Right));
+    }
+    for phase in &phase_names {
+        title.push(phase_title(phase).cell().bold(true).justify(Justify::Right));
+    }
+    for metric in &metric_names {
+        title.push(metric.cell().bold(true).justify(Justify::Right));
+    }
+    if has_allocations {
+        for x in ALLOCATION_TITLES {
+            title.push(x.cell().bold(true).justify(Justify::Right));
+        }
+    }
+    for x in input_titles(has_input_sizes) {
+        title.push(x.cell().bold(true).justify(Justify::Right));
+    }
+    if pareto.is_some() {
+        title.push("Pareto".cell().bold(true).justify(Justify::Center));
+    }
 
     // cells
     let mut rows = vec![];
-    for (i, (input_variant, input_estimates)) in input_levels.iter().zip(estimates).enumerate() {
+    for (i, ((input_variant, input_estimates), input_records)) in
+        input_levels.iter().zip(estimates).zip(records).enumerate()
+    {
+        let throughput = throughputs[i].as_ref();
         let values = || input_estimates.iter().map(|x| x.unwrap_or(f64::MAX));
         let min = values().min_by(cmp).unwrap_or(f64::MAX);
         let max = values().max_by(cmp).unwrap_or(f64::MIN);
@@ -181,13 +471,20 @@ fn print_summary_table<E: Experiment>(
         };
 
         let input_factor_levels = input_variant.factor_levels();
-        for (a, (alg_variant, estimate)) in alg_levels.iter().zip(input_estimates).enumerate() {
+        for (a, ((alg_variant, estimate), record)) in alg_levels
+            .iter()
+            .zip(input_estimates)
+            .zip(input_records)
+            .enumerate()
+        {
             let t = i * alg_levels.len() + a;
             let alg_factor_levels = alg_variant.factor_levels();
             let rank = rank_of(estimate);
-            let estimate = estimate
-                .map(|x| format!("{x:.0}"))
-                .unwrap_or("NA".to_string());
+            let rates: Vec<_> = processed
+                .iter()
+                .map(|kind| format_rate(kind.rate::<M>(throughput, *estimate)))
+                .collect();
+            let estimate = format_time(record.status, *estimate);
             let mut columns = vec![
                 cell_of(&rank, (t + 1).cell()),
                 cell_of(&rank, (i + 1).cell()),
@@ -198,6 +495,40 @@ fn print_summary_table<E: Experiment>(
                 columns.push(cell_of(&rank, x.cell()));
             }
             columns.push(cell_of(&rank, estimate.cell().justify(Justify::Right)));
+            if has_replicates {
+                for x in replicate_columns(record.replicates) {
+                    columns.push(cell_of(&rank, x.cell().justify(Justify::Right)));
+                }
+            }
+            for x in rates {
+                columns.push(cell_of(&rank, x.cell().justify(Justify::Right)));
+            }
+            for phase in &phase_names {
+                let value = record
+                    .phase(phase)
+                    .map(|x| format!("{x:.0}"))
+                    .unwrap_or("NA".to_string());
+                columns.push(cell_of(&rank, value.cell().justify(Justify::Right)));
+            }
+            for metric in &metric_names {
+                let value = format_metric(record.metric(metric));
+                columns.push(cell_of(&rank, value.cell().justify(Justify::Right)));
+            }
+            if has_allocations {
+                for x in allocation_columns(record.allocations) {
+                    columns.push(cell_of(&rank, x.cell().justify(Justify::Right)));
+                }
+            }
+            for x in input_columns(record.input, has_input_sizes) {
+                columns.push(cell_of(&rank, x.cell().justify(Justify::Right)));
+            }
+            if let Some(pareto) = pareto {
+                let mark = match pareto.per_input[i][a] {
+                    true => "*",
+                    false => "",
+                };
+                columns.push(cell_of(&rank, mark.cell().justify(Justify::Center)));
+            }
 
             rows.push(columns);
         }
@@ -206,17 +537,154 @@ fn print_summary_table<E: Experiment>(
     let table = rows.table().title(title);
     let log = format!("\n# {name}");
     println!("{}", log.bold().yellow());
-    print_stdout(table).expect("Failed to print the summary table");
+    print_stdout(table)
+}
+
+fn create_replicates_csv<E: Experiment, M: ExperimentMeasurement>(
+    exp: &E,
+    name: &str,
+    input_levels: &[E::InputFactors],
+    alg_levels: &[E::AlgFactors],
+    instance_estimates: &[Vec<Option<f64>>],
+    instance_records: &[Vec<TreatmentRecord>],
+) -> std::io::Result<()> {
+    let time_title = time_title::<M>();
+    let num_r = exp.num_replicates();
+    let format = exp.csv_format();
+    let path = exp.replicates_csv_path(name);
+    let mut file = create_file(&path)?;
+
+    // title
+    let mut row = vec!["t", "i", "r", "a"];
+    row.extend_from_slice(&<E::InputFactors as Factors>::factor_names());
+    row.extend_from_slice(&<E::AlgFactors as Factors>::factor_names());
+    row.push(&time_title);
+    file.write_all(format.row(&row).as_bytes())?;
+
+    // rows
+    for (k, (input_estimates, input_records)) in
+        instance_estimates.iter().zip(instance_records).enumerate()
+    {
+        let (i, r) = (k / num_r, k % num_r);
+        let input_factor_levels = input_levels[i].factor_levels();
+        for (a, ((alg_variant, estimate), record)) in alg_levels
+            .iter()
+            .zip(input_estimates)
+            .zip(input_records)
+            .enumerate()
+        {
+            let t = i * alg_levels.len() + a;
+            let mut row = vec![
+                (t + 1).to_string(),
+                (i + 1).to_string(),
+                (r + 1).to_string(),
+                (a + 1).to_string(),
+            ];
+            row.extend_from_slice(&input_factor_levels);
+            row.extend(alg_variant.factor_levels());
+            row.push(format_time(record.status, *estimate));
+            file.write_all(format.row(&row).as_bytes())?;
+        }
+    }
+    Ok(())
 }
 
-pub fn create_ai_prompt_to_analyze<E: Experiment>(
+pub(crate) fn create_pareto_csv<E: Experiment, M: ExperimentMeasurement>(
+    exp: &E,
+    name: &str,
+    input_levels: &[E::InputFactors],
+    alg_levels: &[E::AlgFactors],
+    estimates: &[Vec<Option<f64>>],
+    records: &[Vec<TreatmentRecord>],
+    pareto: &ParetoFronts,
+) -> std::io::Result<()> {
+    let time_title = time_title::<M>();
+    let format = exp.csv_format();
+    let path = exp.pareto_csv_path(name);
+    let mut file = create_file(&path)?;
+    let metric_names = metric_names(records);
+    let na = || "NA".to_string();
+
+    // title
+    let mut row = vec!["scope", "dominance", "i", "a"];
+    row.extend_from_slice(&<E::InputFactors as Factors>::factor_names());
+    row.extend_from_slice(&<E::AlgFactors as Factors>::factor_names());
+    row.push(&time_title);
+    row.extend_from_slice(&metric_names);
+    file.write_all(format.row(&row).as_bytes())?;
+
+    // rows of Pareto-optimal variants per input
+    for (i, input_variant) in input_levels.iter().enumerate() {
+        for (a, alg_variant) in alg_levels.iter().enumerate() {
+            if !pareto.per_input[i][a] {
+                continue;
+            }
+            let mut row = vec![
+                "input".to_string(),
+                "input".to_string(),
+                (i + 1).to_string(),
+                (a + 1).to_string(),
+            ];
+            row.extend(input_variant.factor_levels());
+            row.extend(alg_variant.factor_levels());
+            row.push(
+                estimates[i][a]
+                    .map(|x| format!("{x:.0}"))
+                    .unwrap_or_else(na),
+            );
+            for metric in &metric_names {
+                let value = records[i][a].metric(metric);
+                row.push(value.map(|x| x.to_string()).unwrap_or_else(na));
+            }
+            file.write_all(format.row(&row).as_bytes())?;
+        }
+    }
+
+    // rows of Pareto-optimal variants across all inputs, with responses averaged over the inputs;
+    // note that the front is not computed on the averages, but by dominance on the responses of every input
+    let num_inputs = input_levels.len() as f64;
+    let mean = |values: Vec<Option<f64>>| -> Option<f64> {
+        let values: Option<Vec<f64>> = values.into_iter().collect();
+        values.map(|x| x.iter().sum::<f64>() / num_inputs)
+    };
+    for (a, alg_variant) in alg_levels.iter().enumerate() {
+        if !pareto.overall[a] {
+            continue;
+        }
+        let mut row = vec![
+            "all".to_string(),
+            "per_input".to_string(),
+            na(),
+            (a + 1).to_string(),
+        ];
+        row.extend(
+            <E::InputFactors as Factors>::factor_names()
+                .iter()
+                .map(|_| na()),
+        );
+        row.extend(alg_variant.factor_levels());
+        let time = mean(estimates.iter().map(|x| x[a]).collect());
+        row.push(time.map(|x| format!("{x:.0}")).unwrap_or_else(na));
+        for metric in &metric_names {
+            let value = mean(records.iter().map(|x| x[a].metric(metric)).collect());
+            row.push(value.map(|x| x.to_string()).unwrap_or_else(na));
+        }
+        file.write_all(format.row(&row).as_bytes())?;
+    }
+
+    Ok(())
+}
+
+pub fn create_ai_prompt_to_analyze<E: Experiment, M: ExperimentMeasurement>(
     exp: &E,
     name: &str,
     data: &[E::InputFactors],
     variants: &[E::AlgFactors],
+    records: &[Vec<TreatmentRecord>],
 ) -> std::io::Result<()> {
+    let time_title = time_title::<M>();
     let path = exp.ai_prompt_path(name);
-    let mut file = File::create(path)?;
+    let mut file = create_file(&path)?;
 
     let summary_path = exp.summary_csv_path(name);
     let benchmark_path = exp.benchmark_file_path(name);
@@ -225,6 +693,95 @@ pub fn create_ai_prompt_to_analyze<E: Experiment>(
     let num_variants = variants.len();
     let alg_factor_names = <E::AlgFactors as Factors>::factor_names().join(", ");
     let num_treatments = num_inputs * num_variants;
+    let allocations = match has_allocations(records) {
+        true => {
+            r"
+Further, allocations made by a single execution of each treatment are recorded in columns 'Allocs' (number of allocations), 'Alloc (bytes)' (total bytes allocated) and 'Peak (bytes)' (peak live bytes).
+Please also consider the trade-off between time and allocations in your analysis.
+"
+        }
+        false => "",
+    };
+    let throughputs: Vec<_> = data.iter().map(|x| exp.throughput(x)).collect();
+    let processed_titles: Vec<_> = processed_kinds(&throughputs)
+        .iter()
+        .map(|x| x.title::<M>())
+        .collect();
+    let throughput = match processed_titles.is_empty() {
+        true => String::new(),
+        false => format!(
+            r"
+Throughput of each treatment is recorded in columns '{}', computed from the amount of data processed by each execution on the input and the time.
+Throughput allows to compare the variants across data sets of different sizes.
+",
+            processed_titles.join(", ")
+        ),
+    };
+    let replicates = match exp.num_replicates() {
+        1 => String::new(),
+        num_r => format!(
+            r"
+Each data set is replicated by {num_r} randomly generated instances, and each algorithm variant is benchmarked on each instance.
+The time of each treatment is the mean across the replicates, while columns '{}' give the number of replicates with a measured time, and the minimum, maximum and the range relative to the mean of the replicate times.
+When the number of measured replicates is less than {num_r}, the statistics are computed over the measured replicates only.
+Times of the treatments on each replicate are listed in the file at '{:?}'.
+Please also consider the variability of the variants across the replicates in your analysis.
+",
+            replicate_titles::<M>().join("', '"),
+            exp.replicates_csv_path(name),
+        ),
+    };
+    let has_status = |status| records.iter().flatten().any(|x| x.status == status);
+    let timeouts = match has_status(TreatmentStatus::TimedOut)
+        || has_status(TreatmentStatus::Skipped)
+        || has_status(TreatmentStatus::NotRun)
+    {
+        true => {
+            r"
+Some treatments are not measured and their time is given as 'TIMEOUT', 'SKIPPED' or 'NOT RUN'.
+'TIMEOUT' means that a single execution of the treatment exceeded its time limit; hence, the variant is impractically slow for the data set.
+'SKIPPED' means that the treatment is not executed since the time budget of the experiment is exhausted; hence, nothing is known about its performance.
+'NOT RUN' means that the treatment is excluded from this run, such as by the benchmark filter; hence, nothing is known about its performance.
+"
+        }
+        false => "",
+    };
+    let inputs = format!(
+        r"
+Columns '{}' describe the data sets rather than the treatments; they report the time to create each data set and its size, if available.
+These values are repeated for all algorithm variants on the same data set and they are not part of the measured time.
+",
+        input_titles(has_input_sizes(records)).join("', '")
+    );
+    let phase_names = phase_names(records);
+    let phases = match phase_names.is_empty() {
+        true => String::new(),
+        false => format!(
+            r"
+The execution of each treatment consists of phases '{}'.
+Mean time spent in each phase per execution is recorded in the columns with the phase name followed by '(ns)'.
+Please also analyze in which phases the variants spend their time.
+",
+            phase_names.join(", ")
+        ),
+    };
+    let metric_names = metric_names(records);
+    let metrics = match metric_names.is_empty() {
+        true => String::new(),
+        false => format!(
+            r"
+In addition to time, the following metrics are recorded as secondary responses: '{}'.
+They are computed once per treatment from the output of the algorithm, and represent the quality of the solution rather than its speed.
+Please also consider the trade-off between time and these metrics in your analysis.
+Algorithm variants which are Pareto-optimal over time and these metrics are listed in the file at '{:?}'.
+In this file, rows with the 'input' scope are Pareto-optimal on the responses of the input 'i'.
+Rows with the 'all' scope are Pareto-optimal across all inputs by per-input dominance, as given in the 'dominance' column: a variant is dominated only if another variant is at least as good on every input and better on some input.
+Their responses are the means over the inputs; however, the front is not computed on these means.
+",
+            metric_names.join(", "),
+            exp.pareto_csv_path(name),
+        ),
+    };
 
     let prompt = format!(
         r"
@@ -240,10 +797,11 @@ Each algorithm variant gets a unique index specified in column 'a'.
 
 In total, there exist {num_treatments} treatments as unique combinations of input data settings and algorithm variant parameters.
 Each treatment gets a unique index specified in column 't'.
+Column 'master_seed' holds the master seed from which the data sets of the run are generated; it is the same for all treatments.
 
-The response variable is the time.
+The response variable is the time, recorded in column '{time_title}'.
 Although we have a single value per treatment, these values are obtained by the 'criterion' crate which runs sufficiently large number of repetitions to obtain these point estimates.
-
+{replicates}{timeouts}{throughput}{phases}{metrics}{allocations}{inputs}
 The objective is to solve the problem as fast as possible.
 In other words, we want to minimize elapsed time.
 We are searching the best values of the parameters, or best variant, that would perform the best across different data sets.
@@ -0,0 +1,245 @@
+use crate::estimate::Estimate;
+use crate::experiment_sealed::ExperimentSealed;
+use crate::summary::create_file;
+use crate::{Experiment, ExperimentResult, Factors, TreatmentOrder, TreatmentStatus, Validation};
+use std::io::Write;
+
+/// Version of the schema of the json summary, which is incremented whenever a field is removed or its meaning
+/// changes; new fields might be added without changing the version.
+pub(crate) const SUMMARY_JSON_SCHEMA_VERSION: usize = 1;
+
+/// A json value.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Json {
+    Null,
+    Int(u64),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Creates a json object from the `fields`.
+    pub fn object<'a>(fields: impl IntoIterator<Item = (&'a str, Json)>) -> Self {
+        Self::Object(
+            fields
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        )
+    }
+
+    /// Creates a json array of strings.
+    pub fn strings<S: ToString>(values: impl IntoIterator<Item = S>) -> Self {
+        Self::Array(
+            values
+                .into_iter()
+                .map(|x| Self::String(x.to_string()))
+                .collect(),
+        )
+    }
+
+    /// Creates a json number, or null if the `value` is missing or not finite.
+    pub fn number(value: Option<f64>) -> Self {
+        match value {
+            Some(x) if x.is_finite() => Self::Number(x),
+            _ => Self::Null,
+        }
+    }
+
+    /// Writes the value to `out` with two spaces of indentation per level, starting from the `indent` level.
+    pub fn write(&self, out: &mut String, indent: usize) {
+        let pad = |out: &mut String, level: usize| out.push_str(&"  ".repeat(level));
+        match self {
+            Self::Null => out.push_str("null"),
+            Self::Int(x) => out.push_str(&x.to_string()),
+            Self::Number(x) => out.push_str(&format!("{x:?}")),
+            Self::String(x) => write_string(out, x),
+            Self::Array(values) if values.is_empty() => out.push_str("[]"),
+            Self::Array(values) => {
+                out.push_str("[\n");
+                for (idx, value) in values.iter().enumerate() {
+                    pad(out, indent + 1);
+                    value.write(out, indent + 1);
+                    out.push_str(if idx + 1 < values.len() { ",\n" } else { "\n" });
+                }
+                pad(out, indent);
+                out.push(']');
+            }
+            Self::Object(fields) if fields.is_empty() => out.push_str("{}"),
+            Self::Object(fields) => {
+                out.push_str("{\n");
+                for (idx, (key, value)) in fields.iter().enumerate() {
+                    pad(out, indent + 1);
+                    write_string(out, key);
+                    out.push_str(": ");
+                    value.write(out, indent + 1);
+                    out.push_str(if idx + 1 < fields.len() { ",\n" } else { "\n" });
+                }
+                pad(out, indent);
+                out.push('}');
+            }
+        }
+    }
+}
+
+/// Writes the `value` to `out` as a quoted and escaped json string.
+fn write_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Json description of the factors of type `F` with their levels in order of their first appearance in
+/// `variants`.
+fn factors_json<F: Factors>(variants: &[F]) -> Json {
+    let names = F::factor_names();
+    let names_short = F::factor_names_short();
+    let factors = names
+        .iter()
+        .zip(&names_short)
+        .enumerate()
+        .map(|(j, (name, name_short))| {
+            let mut levels: Vec<(String, String)> = vec![];
+            for variant in variants {
+                let level = (
+                    variant.factor_levels()[j].clone(),
+                    variant.factor_levels_short()[j].clone(),
+                );
+                if !levels.contains(&level) {
+                    levels.push(level);
+                }
+            }
+            Json::object([
+                ("name", Json::String(name.to_string())),
+                ("short_name", Json::String(name_short.to_string())),
+                ("levels", Json::strings(levels.iter().map(|x| &x.0))),
+                ("levels_short", Json::strings(levels.iter().map(|x| &x.1))),
+            ])
+        });
+    Json::Array(factors.collect())
+}
+
+/// Json object mapping the factor names of `variant` to its levels.
+fn levels_json<F: Factors>(variant: &F) -> Json {
+    let fields = F::factor_names().into_iter().zip(variant.factor_levels());
+    Json::object(fields.map(|(name, level)| (name, Json::String(level))))
+}
+
+fn treatment_order_json(order: TreatmentOrder) -> Json {
+    let (kind, seed) = match order {
+        TreatmentOrder::Canonical => ("Canonical", None),
+        TreatmentOrder::Randomized { seed } => ("Randomized", Some(seed)),
+        TreatmentOrder::BlockedRandomized { seed } => ("BlockedRandomized", Some(seed)),
+    };
+    Json::object([
+        ("kind", Json::String(kind.to_string())),
+        ("seed", seed.map(Json::Int).unwrap_or(Json::Null)),
+    ])
+}
+
+fn estimate_json(point: Option<f64>, estimate: Option<Estimate>) -> Json {
+    match (point, estimate) {
+        (Some(point), Some(x)) => Json::object([
+            ("statistic", Json::String(x.statistic.to_string())),
+            ("point", Json::number(Some(point))),
+            ("lower_bound", Json::number(Some(x.lower_bound))),
+            ("upper_bound", Json::number(Some(x.upper_bound))),
+            ("confidence_level", Json::number(Some(x.confidence_level))),
+        ]),
+        _ => Json::Null,
+    }
+}
+
+/// Creates the json summary of the experiment; see the "Summary - JSON" section of the crate documentation
+/// for its schema.
+pub(crate) fn summary_json<E: Experiment>(
+    exp: &E,
+    result: &ExperimentResult<'_, E>,
+    estimates: &[Vec<Option<Estimate>>],
+) -> Json {
+    let num_a = result.alg_levels.len();
+    let treatments = result.treatments().iter().map(|x| {
+        let status = match x.status {
+            TreatmentStatus::Measured => "Measured",
+            TreatmentStatus::TimedOut => "TimedOut",
+            TreatmentStatus::Skipped => "Skipped",
+            TreatmentStatus::NotRun => "NotRun",
+        };
+        let validation = match x.validation {
+            Validation::Matched => "Matched",
+            Validation::Unchecked => "Unchecked",
+            Validation::NotExecuted => "NotExecuted",
+        };
+        let estimate = estimates
+            .get(x.i - 1)
+            .and_then(|input_estimates| input_estimates.get(x.a - 1))
+            .copied()
+            .flatten();
+        let metrics = x
+            .metrics
+            .iter()
+            .map(|(name, value)| (*name, Json::number(Some(*value))));
+        Json::object([
+            ("t", Json::Int(x.t as u64)),
+            ("i", Json::Int(x.i as u64)),
+            ("a", Json::Int(x.a as u64)),
+            ("key_long", Json::String(exp.run_key_long(x.input, x.alg))),
+            ("key_short", Json::String(exp.run_key_short(x.input, x.alg))),
+            ("input_levels", levels_json(x.input)),
+            ("alg_levels", levels_json(x.alg)),
+            ("status", Json::String(status.to_string())),
+            ("validation", Json::String(validation.to_string())),
+            ("estimate", estimate_json(x.estimate, estimate)),
+            (
+                "rank",
+                x.rank.map(|r| Json::Int(r as u64)).unwrap_or(Json::Null),
+            ),
+            ("metrics", Json::object(metrics)),
+        ])
+    });
+
+    Json::object([
+        (
+            "schema_version",
+            Json::Int(SUMMARY_JSON_SCHEMA_VERSION as u64),
+        ),
+        ("name", Json::String(result.name.clone())),
+        ("time_unit", Json::String(result.time_unit.to_string())),
+        ("master_seed", Json::Int(exp.master_seed())),
+        ("num_replicates", Json::Int(exp.num_replicates() as u64)),
+        (
+            "treatment_order",
+            treatment_order_json(exp.treatment_order()),
+        ),
+        ("num_inputs", Json::Int(result.input_levels.len() as u64)),
+        ("num_variants", Json::Int(num_a as u64)),
+        ("input_factors", factors_json(result.input_levels)),
+        ("alg_factors", factors_json(result.alg_levels)),
+        ("treatments", Json::Array(treatments.collect())),
+    ])
+}
+
+/// Writes the json summary of the experiment to the "summary_{name}.json" file.
+pub(crate) fn create_summary_json<E: Experiment>(
+    exp: &E,
+    result: &ExperimentResult<'_, E>,
+    estimates: &[Vec<Option<Estimate>>],
+) -> std::io::Result<()> {
+    let path = exp.summary_json_path(&result.name);
+    let mut json = String::new();
+    summary_json(exp, result, estimates).write(&mut json, 0);
+    json.push('\n');
+    create_file(&path)?.write_all(json.as_bytes())
+}
@@ -0,0 +1,8 @@
+use crate::measure_allocations;
+
+#[test]
+fn allocations_are_not_measured_without_counting_allocator() {
+    let (vec, stats) = measure_allocations(|| vec![1, 2, 3]);
+    assert_eq!(vec, [1, 2, 3]);
+    assert_eq!(stats, None);
+}
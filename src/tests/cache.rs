@@ -0,0 +1,85 @@
+use crate::{CachedInput, InputCache};
+use std::fmt::Debug;
+use std::path::PathBuf;
+
+fn round_trip<T: CachedInput + PartialEq + Debug>(value: T) {
+    let mut bytes = vec![];
+    value.write_to(&mut bytes).expect("writes to vec");
+    let loaded = T::read_from(&mut bytes.as_slice()).expect("reads what is written");
+    assert_eq!(loaded, value);
+}
+
+fn cache_path(file_name: &str) -> PathBuf {
+    std::env::temp_dir()
+        .join("orx-criterion-tests")
+        .join(file_name)
+}
+
+#[test]
+fn cached_input_round_trip() {
+    round_trip(42u8);
+    round_trip(-42i64);
+    round_trip(usize::MAX);
+    round_trip(-3isize);
+    round_trip(0.25f64);
+    round_trip(true);
+    round_trip("input:α".to_string());
+    round_trip(vec![vec![1u32, 2], vec![], vec![3]]);
+    round_trip((7usize, vec![1.5f32]));
+    round_trip((1u8, "x".to_string(), false));
+}
+
+#[test]
+fn cached_input_truncated() {
+    let mut bytes = vec![];
+    vec![1u64, 2, 3]
+        .write_to(&mut bytes)
+        .expect("writes to vec");
+    bytes.truncate(bytes.len() - 1);
+    assert!(Vec::<u64>::read_from(&mut bytes.as_slice()).is_err());
+}
+
+#[test]
+fn input_cache_store_and_load() {
+    let path = cache_path("store_and_load.bin");
+    let cache = InputCache::<Vec<u64>>::new("1");
+    let input = vec![3, 1, 2];
+
+    cache.store(&path, "len:3", 42, &input).expect("stores");
+    let loaded = cache.load(&path, "len:3", 42).expect("loads");
+    assert_eq!(loaded, Some(input));
+}
+
+#[test]
+fn input_cache_misses() {
+    let path = cache_path("misses.bin");
+    let cache = InputCache::<Vec<u64>>::new("1");
+    cache
+        .store(&path, "len:3", 42, &vec![3, 1, 2])
+        .expect("stores");
+
+    // different key, seed or version
+    assert_eq!(cache.load(&path, "len:4", 42).expect("loads"), None);
+    assert_eq!(cache.load(&path, "len:3", 7).expect("loads"), None);
+    let cache_v2 = InputCache::<Vec<u64>>::new("2");
+    assert_eq!(cache_v2.version(), "2");
+    assert_eq!(cache_v2.load(&path, "len:3", 42).expect("loads"), None);
+
+    // missing file
+    let missing = cache_path("missing.bin");
+    assert_eq!(cache.load(&missing, "len:3", 42).expect("loads"), None);
+}
+
+#[test]
+fn input_cache_corrupt() {
+    let path = cache_path("corrupt.bin");
+    let cache = InputCache::<Vec<u64>>::new("1");
+    cache
+        .store(&path, "len:3", 42, &vec![3, 1, 2])
+        .expect("stores");
+
+    let mut bytes = std::fs::read(&path).expect("exists");
+    bytes.truncate(bytes.len() - 4);
+    std::fs::write(&path, bytes).expect("writes");
+    assert!(cache.load(&path, "len:3", 42).is_err());
+}
@@ -0,0 +1,188 @@
+use crate::estimate::Estimate;
+use crate::experiment_sealed::ExperimentSealed;
+use crate::tests::fixtures::{Len, Sum, quick_criterion, read_summary_csv, summary_cell};
+use crate::{Experiment, TreatmentConfig, TreatmentStatus};
+use criterion::SamplingMode;
+use std::cell::RefCell;
+use std::time::Duration;
+
+/// Samples the treatment on the input of length 10 flat 12 times, and applies the `long_input` configuration to
+/// the treatment on the input of length 20.
+struct ConfiguredExperiment {
+    long_input: TreatmentConfig,
+    configured: RefCell<Vec<usize>>,
+}
+
+impl ConfiguredExperiment {
+    fn new(long_input: TreatmentConfig) -> Self {
+        Self {
+            long_input,
+            configured: RefCell::default(),
+        }
+    }
+}
+
+impl Experiment for ConfiguredExperiment {
+    type InputFactors = Len;
+
+    type AlgFactors = Sum;
+
+    type Input = Vec<usize>;
+
+    type Output = usize;
+
+    fn input(&mut self, input_variant: &Self::InputFactors) -> Self::Input {
+        (0..input_variant.0).collect()
+    }
+
+    fn execute(&mut self, _: &Self::AlgFactors, input: &Self::Input) -> Self::Output {
+        input.iter().sum()
+    }
+
+    fn configure(
+        &self,
+        input_variant: &Self::InputFactors,
+        _: &Self::AlgFactors,
+    ) -> TreatmentConfig {
+        self.configured.borrow_mut().push(input_variant.0);
+        match input_variant.0 {
+            10 => TreatmentConfig {
+                sample_size: Some(12),
+                sampling_mode: Some(SamplingMode::Flat),
+                ..Default::default()
+            },
+            _ => self.long_input,
+        }
+    }
+}
+
+/// Sampling mode and number of samples of the treatment as stored by criterion.
+fn read_sample(exp: &ConfiguredExperiment, name: &str, input_variant: &Len) -> (String, usize) {
+    let path = exp
+        .run_estimates_path(name, input_variant, 0, &Sum)
+        .with_file_name("sample.json");
+    let sample = std::fs::read_to_string(path).expect("sample is stored by criterion");
+    let mode = ["Flat", "Linear"]
+        .into_iter()
+        .find(|x| sample.contains(&format!("\"sampling_mode\":\"{x}\"")))
+        .expect("sampling mode is stored");
+    let iters = &sample[sample.find("\"iters\":[").expect("iters are stored")..];
+    let num_samples = iters[..iters.find(']').expect("iters is an array")]
+        .matches(',')
+        .count()
+        + 1;
+    (mode.to_string(), num_samples)
+}
+
+#[test]
+fn treatments_are_configured() {
+    let name = "orx_criterion_test_configure";
+    let input_levels = [Len(10), Len(20)];
+    let mut exp = ConfiguredExperiment::new(TreatmentConfig {
+        sampling_mode: Some(SamplingMode::Linear),
+        ..Default::default()
+    });
+    let mut c = quick_criterion();
+
+    let result = exp
+        .try_bench(&mut c, name, &input_levels, &[Sum])
+        .expect("experiment is reported");
+
+    // configurations are validated before running the treatments, and applied while running them
+    assert_eq!(*exp.configured.borrow(), [10, 20, 10, 20]);
+    assert!(
+        result
+            .treatments()
+            .iter()
+            .all(|x| x.status == TreatmentStatus::Measured && x.estimate.is_some())
+    );
+
+    let summary = read_summary_csv(name);
+    for row in 1..=2 {
+        let time = summary_cell(&summary, row, "Time (ns)");
+        assert!(time.parse::<f64>().is_ok(), "{time} is not measured");
+    }
+
+    assert_eq!(read_sample(&exp, name, &Len(10)), ("Flat".to_string(), 12));
+    // the sample size set for the previous treatment is kept since it is not set again
+    assert_eq!(
+        read_sample(&exp, name, &Len(20)),
+        ("Linear".to_string(), 12)
+    );
+}
+
+#[test]
+fn invalid_configurations_are_rejected() {
+    let cases = [
+        (
+            TreatmentConfig {
+                sample_size: Some(5),
+                ..Default::default()
+            },
+            "sample size must be at least 10",
+        ),
+        (
+            TreatmentConfig {
+                measurement_time: Some(Duration::ZERO),
+                ..Default::default()
+            },
+            "measurement time must be positive",
+        ),
+        (
+            TreatmentConfig {
+                nresamples: Some(0),
+                ..Default::default()
+            },
+            "number of resamples must be positive",
+        ),
+        (
+            TreatmentConfig {
+                confidence_level: Some(1.0),
+                ..Default::default()
+            },
+            "confidence level must be between 0 and 1",
+        ),
+        (
+            TreatmentConfig {
+                noise_threshold: Some(-0.1),
+                ..Default::default()
+            },
+            "noise threshold must not be negative",
+        ),
+    ];
+
+    for (config, reason) in cases {
+        let name = "orx_criterion_test_invalid_config";
+        let mut c = quick_criterion();
+        let result =
+            ConfiguredExperiment::new(config).try_bench(&mut c, name, &[Len(10), Len(20)], &[Sum]);
+        let Err(error) = result else {
+            unreachable!("invalid configuration must be rejected");
+        };
+        assert_eq!(
+            error.to_string(),
+            format!("invalid configuration of the treatment len:20/alg:sum: {reason}")
+        );
+    }
+}
+
+#[test]
+fn statistical_settings_are_configured() {
+    let config = TreatmentConfig {
+        nresamples: Some(500),
+        confidence_level: Some(0.9),
+        significance_level: Some(0.01),
+        ..Default::default()
+    };
+    assert_eq!(config.validate(), Ok(()));
+
+    let name = "orx_criterion_test_statistical_config";
+    let mut exp = ConfiguredExperiment::new(config);
+    let mut c = quick_criterion();
+    exp.try_bench(&mut c, name, &[Len(10), Len(20)], &[Sum])
+        .expect("experiment is reported");
+
+    let estimate = Estimate::read(&exp.run_estimates_path(name, &Len(20), 0, &Sum))
+        .expect("treatment is measured");
+    assert!((estimate.confidence_level - 0.9).abs() < 1e-9);
+}
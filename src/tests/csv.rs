@@ -0,0 +1,94 @@
+use crate::tests::fixtures::parse_csv;
+use crate::{CsvFormat, Error};
+
+#[test]
+fn default_csv_format() {
+    let format = CsvFormat::default();
+    assert_eq!(format.row(&["1", "len", "NA"]), "1,len,NA\n");
+}
+
+#[test]
+fn quote_fields_as_rfc_4180() {
+    let format = CsvFormat::default();
+    assert_eq!(format.quote("Mid"), "Mid");
+    assert_eq!(format.quote("(1, 2)"), "\"(1, 2)\"");
+    assert_eq!(format.quote("say \"hi\""), "\"say \"\"hi\"\"\"");
+    assert_eq!(format.quote("a\nb"), "\"a\nb\"");
+    assert_eq!(format.quote("a\rb"), "\"a\rb\"");
+    assert_eq!(format.quote(""), "");
+}
+
+#[test]
+fn row_with_debug_levels() {
+    let format = CsvFormat::default();
+    let levels = [format!("{:?}", (1, "a")), format!("{:?}", vec![1, 2])];
+    assert_eq!(format.row(&levels), "\"(1, \"\"a\"\")\",\"[1, 2]\"\n");
+}
+
+#[test]
+fn custom_delimiter() {
+    let format = CsvFormat {
+        delimiter: ';',
+        long_format: false,
+    };
+    assert_eq!(format.row(&["(1, 2)", "a;b", "c"]), "(1, 2);\"a;b\";c\n");
+
+    let format = CsvFormat {
+        delimiter: '\t',
+        long_format: false,
+    };
+    assert_eq!(format.row(&["a b", "c\td"]), "a b\t\"c\td\"\n");
+}
+
+#[test]
+fn reject_quote_and_line_break_delimiters() {
+    for delimiter in [',', ';', '\t', '|'] {
+        let format = CsvFormat {
+            delimiter,
+            long_format: false,
+        };
+        assert!(format.validate().is_ok());
+    }
+
+    for delimiter in ['"', '\n', '\r'] {
+        let format = CsvFormat {
+            delimiter,
+            long_format: false,
+        };
+        assert!(matches!(
+            format.validate(),
+            Err(Error::InvalidCsvDelimiter(x)) if x == delimiter
+        ));
+    }
+}
+
+#[test]
+fn invalid_delimiter_error() {
+    let format = CsvFormat {
+        delimiter: '"',
+        long_format: false,
+    };
+    let Err(error) = format.validate() else {
+        unreachable!("double quote delimiter must be rejected");
+    };
+    assert_eq!(
+        error.to_string(),
+        "csv delimiter must not be a double quote or a line break, but it is '\"'"
+    );
+}
+
+#[test]
+fn quoted_rows_are_parsed_back() {
+    let fields = ["(1, 2)", "say \"hi\"", "a\nb", "", "NA"];
+    for delimiter in [',', ';', '\t'] {
+        let format = CsvFormat {
+            delimiter,
+            long_format: false,
+        };
+        let csv = [format.row(&fields), format.row(&["x"; 5])].concat();
+        assert_eq!(
+            parse_csv(&csv, delimiter),
+            [fields.map(String::from), ["x"; 5].map(String::from)]
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use crate::Error;
+use crate::record::TreatmentRecord;
+use crate::summary::try_summarize;
+use crate::tests::fixtures::{Len, Sum, SumExperiment};
+use criterion::measurement::WallTime;
+use std::error::Error as _;
+use std::path::PathBuf;
+
+#[test]
+fn error_display() {
+    let source = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied");
+    let error = Error::write(PathBuf::from("summary.csv"))(source);
+    assert_eq!(error.to_string(), "failed to write \"summary.csv\": denied");
+    assert!(error.source().is_some());
+
+    let error = Error::Print(std::io::Error::other("closed"));
+    assert_eq!(
+        error.to_string(),
+        "failed to print to the standard output: closed"
+    );
+}
+
+#[test]
+fn summarize_creates_missing_directories() {
+    let name = "orx_criterion_test_missing_dirs";
+    let dir: PathBuf = ["target", "criterion", name].iter().collect();
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let input_levels = [Len(1), Len(2)];
+    let alg_levels = [Sum];
+    let records = vec![vec![TreatmentRecord::default()]; 2];
+
+    let result =
+        try_summarize::<_, WallTime>(&SumExperiment, name, &input_levels, &alg_levels, &records)
+            .expect("summary is created");
+
+    let summary = std::fs::read_to_string(dir.join(format!("summary_{name}.csv")))
+        .expect("summary csv is created");
+    // the header is the first row of the csv
+    assert!(summary.starts_with("t,i,a,len,alg,master_seed,Time (ns),"));
+    assert!(result.treatments().iter().all(|x| x.estimate.is_none()));
+    let _ = std::fs::remove_dir_all(&dir);
+}
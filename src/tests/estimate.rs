@@ -0,0 +1,62 @@
+use crate::estimate::{Estimate, aggregate_estimates};
+
+const ESTIMATES_WITH_SLOPE: &str = r#"{"mean":{"confidence_interval":{"confidence_level":0.95,"lower_bound":11.0,"upper_bound":13.0},"point_estimate":12.0,"standard_error":0.5},"median":{"confidence_interval":{"confidence_level":0.95,"lower_bound":10.0,"upper_bound":12.0},"point_estimate":11.0,"standard_error":0.5},"slope":{"confidence_interval":{"confidence_level":0.95,"lower_bound":9.5,"upper_bound":10.5},"point_estimate":10.0,"standard_error":0.25}}"#;
+
+const ESTIMATES_WITHOUT_SLOPE: &str = r#"{"mean":{"confidence_interval":{"confidence_level":0.99,"lower_bound":11.0,"upper_bound":13.0},"point_estimate":12.0,"standard_error":0.5},"slope":null}"#;
+
+fn estimate(point: f64, lower_bound: f64, upper_bound: f64) -> Estimate {
+    Estimate {
+        statistic: "slope",
+        point,
+        lower_bound,
+        upper_bound,
+        confidence_level: 0.95,
+    }
+}
+
+#[test]
+fn parse_slope_estimate() {
+    assert_eq!(
+        Estimate::parse(ESTIMATES_WITH_SLOPE),
+        Some(estimate(10.0, 9.5, 10.5))
+    );
+}
+
+#[test]
+fn parse_mean_estimate_when_slope_is_null() {
+    let expected = Estimate {
+        statistic: "mean",
+        point: 12.0,
+        lower_bound: 11.0,
+        upper_bound: 13.0,
+        confidence_level: 0.99,
+    };
+    assert_eq!(Estimate::parse(ESTIMATES_WITHOUT_SLOPE), Some(expected));
+}
+
+#[test]
+fn parse_invalid_estimate() {
+    assert_eq!(Estimate::parse(""), None);
+    assert_eq!(Estimate::parse(r#"{"slope":{"point_estimate":1.0}}"#), None);
+}
+
+#[test]
+fn aggregate_replicate_estimates() {
+    assert_eq!(Estimate::aggregate(&[]), None);
+
+    let replicates = [estimate(10.0, 9.0, 11.0), estimate(20.0, 18.0, 21.0)];
+    assert_eq!(
+        Estimate::aggregate(&replicates),
+        Some(estimate(15.0, 9.0, 21.0))
+    );
+
+    // two replicates of a single input with two variants, the second of which is measured only once
+    let estimates = vec![
+        vec![Some(replicates[0]), Some(replicates[0])],
+        vec![Some(replicates[1]), None],
+    ];
+    assert_eq!(
+        aggregate_estimates(2, &estimates),
+        vec![vec![Some(estimate(15.0, 9.0, 21.0)), Some(replicates[0])]]
+    );
+}
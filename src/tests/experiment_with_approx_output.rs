@@ -0,0 +1,113 @@
+use crate::{Experiment, Factors, Tolerance};
+
+struct NumTerms(usize);
+
+impl Factors for NumTerms {
+    fn factor_names() -> Vec<&'static str> {
+        vec!["num_terms"]
+    }
+
+    fn factor_levels(&self) -> Vec<String> {
+        vec![self.0.to_string()]
+    }
+}
+
+enum SumOrder {
+    Forwards,
+    Backwards,
+}
+
+impl Factors for SumOrder {
+    fn factor_names() -> Vec<&'static str> {
+        vec!["order"]
+    }
+
+    fn factor_levels(&self) -> Vec<String> {
+        vec![
+            match self {
+                Self::Forwards => "fwd",
+                Self::Backwards => "bwd",
+            }
+            .to_string(),
+        ]
+    }
+}
+
+struct HarmonicSumExperiment;
+
+impl Experiment for HarmonicSumExperiment {
+    type InputFactors = NumTerms;
+
+    type AlgFactors = SumOrder;
+
+    type Input = Vec<f64>;
+
+    type Output = f64;
+
+    fn input(&mut self, num_terms: &Self::InputFactors) -> Self::Input {
+        (1..=num_terms.0).map(|i| 1.0 / i as f64).collect()
+    }
+
+    fn execute(&mut self, order: &Self::AlgFactors, input: &Self::Input) -> Self::Output {
+        match order {
+            SumOrder::Forwards => input.iter().sum(),
+            SumOrder::Backwards => input.iter().rev().sum(),
+        }
+    }
+
+    fn expected_output(&self, _: &Self::InputFactors, input: &Self::Input) -> Option<Self::Output> {
+        Some(input.iter().sum())
+    }
+
+    fn outputs_match(&self, expected: &Self::Output, actual: &Self::Output) -> Result<(), String> {
+        Tolerance::relative(1e-9).check(*expected, *actual)
+    }
+}
+
+#[test]
+fn basic_experiment_with_approx_output() {
+    let mut exp = HarmonicSumExperiment;
+    let data = [NumTerms(10), NumTerms(100_000)];
+    let variants = [SumOrder::Forwards, SumOrder::Backwards];
+
+    for datum in &data {
+        let input = exp.input(datum);
+        let expected = exp
+            .expected_output(datum, &input)
+            .expect("expected output is defined");
+        for variant in &variants {
+            let output = exp.execute(variant, &input);
+            assert!(exp.outputs_match(&expected, &output).is_ok());
+        }
+    }
+
+    assert!(exp.outputs_match(&1.0, &1.1).is_err());
+}
+
+#[test]
+fn default_outputs_match_is_exact() {
+    struct ExactExperiment;
+
+    impl Experiment for ExactExperiment {
+        type InputFactors = NumTerms;
+
+        type AlgFactors = SumOrder;
+
+        type Input = ();
+
+        type Output = Vec<usize>;
+
+        fn input(&mut self, _: &Self::InputFactors) -> Self::Input {}
+
+        fn execute(&mut self, _: &Self::AlgFactors, _: &Self::Input) -> Self::Output {
+            vec![]
+        }
+    }
+
+    let exp = ExactExperiment;
+    assert!(exp.outputs_match(&vec![1, 2], &vec![1, 2]).is_ok());
+    assert_eq!(
+        exp.outputs_match(&vec![1, 2], &vec![2, 1]),
+        Err(crate::debug_diff(&vec![1, 2], &vec![2, 1]))
+    );
+}
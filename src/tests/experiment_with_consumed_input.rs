@@ -0,0 +1,120 @@
+use crate::record::record_treatment;
+use crate::tests::fixtures::Len;
+use crate::{ExecutionMode, Experiment, Factors};
+use criterion::BatchSize;
+
+enum SortMethod {
+    Stable,
+    Unstable,
+}
+
+impl Factors for SortMethod {
+    fn factor_names() -> Vec<&'static str> {
+        vec!["sort"]
+    }
+
+    fn factor_levels(&self) -> Vec<String> {
+        vec![
+            match self {
+                Self::Stable => "stable",
+                Self::Unstable => "unstable",
+            }
+            .to_string(),
+        ]
+    }
+}
+
+struct InPlaceSortExperiment;
+
+impl Experiment for InPlaceSortExperiment {
+    type InputFactors = Len;
+
+    type AlgFactors = SortMethod;
+
+    type Input = Vec<usize>;
+
+    type Output = Vec<usize>;
+
+    fn input(&mut self, len: &Self::InputFactors) -> Self::Input {
+        (0..len.0).rev().collect()
+    }
+
+    fn execute(&mut self, alg_variant: &Self::AlgFactors, input: &Self::Input) -> Self::Output {
+        self.execute_mut(alg_variant, input.clone())
+    }
+
+    fn execute_mut(
+        &mut self,
+        alg_variant: &Self::AlgFactors,
+        mut input: Self::Input,
+    ) -> Self::Output {
+        match alg_variant {
+            SortMethod::Stable => input.sort(),
+            SortMethod::Unstable => input.sort_unstable(),
+        }
+        input
+    }
+
+    fn execution_mode(&self) -> ExecutionMode<Self::Input> {
+        ExecutionMode::Consumed {
+            setup: Clone::clone,
+            batch_size: BatchSize::SmallInput,
+        }
+    }
+
+    fn expected_output(&self, len: &Self::InputFactors, _: &Self::Input) -> Option<Self::Output> {
+        Some((0..len.0).collect())
+    }
+
+    fn metrics(
+        &self,
+        _: &Self::InputFactors,
+        input: &Self::Input,
+        output: &Self::Output,
+    ) -> Vec<(&'static str, f64)> {
+        vec![
+            ("first", output[0] as f64),
+            ("input_first", input[0] as f64),
+        ]
+    }
+}
+
+#[test]
+fn consumed_input_experiment() {
+    let mut exp = InPlaceSortExperiment;
+    let len = Len(10);
+    let input = exp.input(&len);
+
+    for variant in [SortMethod::Stable, SortMethod::Unstable] {
+        let ExecutionMode::Consumed { setup, .. } = exp.execution_mode() else {
+            unreachable!("consumed mode is defined")
+        };
+        let output = exp.execute_mut(&variant, setup(&input));
+        assert_eq!(output, (0..10).collect::<Vec<_>>());
+
+        let record = record_treatment(&mut exp, "consumed", &len, 0, &variant, &input);
+        assert_eq!(record.metric("first"), Some(0.0));
+        assert_eq!(record.metric("input_first"), Some(9.0));
+        assert!(record.validated);
+    }
+
+    // the input is not mutated by the consuming executions
+    assert_eq!(input, (0..10).rev().collect::<Vec<_>>());
+}
+
+#[test]
+fn execution_mode_is_debug_and_clone() {
+    let mode = InPlaceSortExperiment.execution_mode();
+    let cloned = mode.clone();
+
+    let ExecutionMode::Consumed { setup, batch_size } = cloned else {
+        unreachable!("consumed mode is defined")
+    };
+    assert_eq!(setup(&vec![2, 1]), [2, 1]);
+    assert_eq!(batch_size, BatchSize::SmallInput);
+    assert!(format!("{mode:?}").starts_with("Consumed { setup: "));
+    assert_eq!(
+        format!("{:?}", ExecutionMode::<Vec<usize>>::Borrowed),
+        "Borrowed"
+    );
+}
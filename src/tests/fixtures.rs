@@ -0,0 +1,166 @@
+use crate::experiment_sealed::ExperimentSealed;
+use crate::record::TreatmentRecord;
+use crate::{Experiment, ExperimentResult, Factors};
+use criterion::Criterion;
+use std::time::Duration;
+
+/// Length of the input array.
+pub struct Len(pub usize);
+
+impl Factors for Len {
+    fn factor_names() -> Vec<&'static str> {
+        vec!["len"]
+    }
+
+    fn factor_names_short() -> Vec<&'static str> {
+        vec!["l"]
+    }
+
+    fn factor_levels(&self) -> Vec<String> {
+        vec![self.0.to_string()]
+    }
+}
+
+/// Single algorithm variant summing up the input.
+pub struct Sum;
+
+impl Factors for Sum {
+    fn factor_names() -> Vec<&'static str> {
+        vec!["alg"]
+    }
+
+    fn factor_levels(&self) -> Vec<String> {
+        vec!["sum".to_string()]
+    }
+}
+
+/// Size of the chunks which are summed up separately.
+#[derive(Debug, PartialEq)]
+pub struct Chunk(pub usize);
+
+impl Factors for Chunk {
+    fn factor_names() -> Vec<&'static str> {
+        vec!["chunk"]
+    }
+
+    fn factor_names_short() -> Vec<&'static str> {
+        vec!["c"]
+    }
+
+    fn factor_levels(&self) -> Vec<String> {
+        vec![self.0.to_string()]
+    }
+}
+
+pub struct SumExperiment;
+
+impl Experiment for SumExperiment {
+    type InputFactors = Len;
+
+    type AlgFactors = Sum;
+
+    type Input = Vec<usize>;
+
+    type Output = usize;
+
+    fn input(&mut self, input_variant: &Self::InputFactors) -> Self::Input {
+        (0..input_variant.0).collect()
+    }
+
+    fn execute(&mut self, _: &Self::AlgFactors, input: &Self::Input) -> Self::Output {
+        input.iter().sum()
+    }
+}
+
+pub struct ChunkedSum;
+
+impl Experiment for ChunkedSum {
+    type InputFactors = Len;
+
+    type AlgFactors = Chunk;
+
+    type Input = Vec<u64>;
+
+    type Output = u64;
+
+    fn input(&mut self, input_variant: &Self::InputFactors) -> Self::Input {
+        (0..input_variant.0 as u64).collect()
+    }
+
+    fn execute(&mut self, alg_variant: &Self::AlgFactors, input: &Self::Input) -> Self::Output {
+        input
+            .chunks(alg_variant.0)
+            .map(|x| x.iter().sum::<u64>())
+            .sum()
+    }
+}
+
+pub const INPUTS: [Len; 2] = [Len(10), Len(1000)];
+
+pub const ALGS: [Chunk; 3] = [Chunk(1), Chunk(4), Chunk(64)];
+
+/// Result of the [`ChunkedSum`] experiment over [`INPUTS`] and [`ALGS`] with the given estimates and records.
+pub fn chunked_sum_result(
+    estimates: &[Vec<Option<f64>>],
+    records: &[Vec<TreatmentRecord>],
+) -> ExperimentResult<'static, ChunkedSum> {
+    ExperimentResult::new("chunks", "ns", &INPUTS, &ALGS, estimates, records)
+}
+
+/// Criterion runner with the shortest settings to run experiments in tests.
+pub fn quick_criterion() -> Criterion {
+    Criterion::default()
+        .sample_size(10)
+        .warm_up_time(Duration::from_millis(1))
+        .measurement_time(Duration::from_millis(10))
+        .nresamples(1000)
+        .without_plots()
+}
+
+/// Reads the comma delimited summary csv of the experiment with the given `name` as a list of rows, where the
+/// first row is the header.
+pub fn read_summary_csv(name: &str) -> Vec<Vec<String>> {
+    let csv = std::fs::read_to_string(SumExperiment.summary_csv_path(name))
+        .expect("summary csv is created");
+    parse_csv(&csv, ',')
+}
+
+/// Parses the `csv` with fields quoted as defined by RFC 4180 into a list of rows.
+pub fn parse_csv(csv: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = csv.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (in_quotes, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => in_quotes = false,
+            (true, c) => field.push(c),
+            (false, '"') => in_quotes = true,
+            (false, '\n') => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            (false, c) if c == delimiter => row.push(std::mem::take(&mut field)),
+            (false, c) => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
+
+/// Value of the `column` of the `row` of the summary csv.
+pub fn summary_cell<'a>(summary: &'a [Vec<String>], row: usize, column: &str) -> &'a str {
+    let j = summary[0]
+        .iter()
+        .position(|x| x == column)
+        .expect("column exists");
+    &summary[row][j]
+}
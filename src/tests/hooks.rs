@@ -0,0 +1,69 @@
+use crate::Experiment;
+use crate::hooks::release_inputs;
+use crate::tests::fixtures::{Len, Sum};
+
+#[derive(Default)]
+struct HookedExperiment {
+    num_replicates: usize,
+    released: Vec<usize>,
+}
+
+impl Experiment for HookedExperiment {
+    type InputFactors = Len;
+
+    type AlgFactors = Sum;
+
+    type Input = Vec<usize>;
+
+    type Output = usize;
+
+    fn input(&mut self, input_variant: &Self::InputFactors) -> Self::Input {
+        (0..input_variant.0).collect()
+    }
+
+    fn num_replicates(&self) -> usize {
+        self.num_replicates
+    }
+
+    fn execute(&mut self, _: &Self::AlgFactors, input: &Self::Input) -> Self::Output {
+        input.iter().sum()
+    }
+
+    fn after_input(&mut self, input_variant: &Self::InputFactors) -> Option<String> {
+        self.released.push(input_variant.0);
+        None
+    }
+}
+
+#[test]
+fn release_completed_inputs() {
+    let mut exp = HookedExperiment {
+        num_replicates: 1,
+        ..Default::default()
+    };
+    let input_levels = [Len(1), Len(2), Len(3)];
+    let mut inputs = vec![Some(vec![0]), None, Some(vec![0, 1, 2])];
+
+    release_inputs(&mut exp, &input_levels, &mut inputs, &[1, 0, 0]);
+    assert_eq!(inputs, [Some(vec![0]), None, None]);
+    assert_eq!(exp.released, [3]);
+
+    // released inputs are not released again
+    release_inputs(&mut exp, &input_levels, &mut inputs, &[0, 0, 0]);
+    assert_eq!(inputs, [None, None, None]);
+    assert_eq!(exp.released, [3, 1]);
+}
+
+#[test]
+fn release_completed_replicates() {
+    let mut exp = HookedExperiment {
+        num_replicates: 2,
+        ..Default::default()
+    };
+    let input_levels = [Len(1), Len(2)];
+    let mut inputs = vec![Some(vec![0]), Some(vec![0]), Some(vec![0, 1]), None];
+
+    release_inputs(&mut exp, &input_levels, &mut inputs, &[1, 0, 0, 0]);
+    assert_eq!(inputs, [Some(vec![0]), None, None, None]);
+    assert_eq!(exp.released, [1, 2]);
+}
@@ -0,0 +1,68 @@
+use crate::ExperimentMeasurement;
+use crate::measurement::time_title;
+use criterion::Throughput;
+use criterion::measurement::{Measurement, ValueFormatter, WallTime};
+
+/// A measurement counting the number of measured calls.
+struct Calls;
+
+struct CallsFormatter;
+
+impl ValueFormatter for CallsFormatter {
+    fn scale_values(&self, _: f64, _: &mut [f64]) -> &'static str {
+        "calls"
+    }
+
+    fn scale_throughputs(&self, _: f64, _: &Throughput, _: &mut [f64]) -> &'static str {
+        "calls"
+    }
+
+    fn scale_for_machines(&self, _: &mut [f64]) -> &'static str {
+        "calls"
+    }
+}
+
+impl Measurement for Calls {
+    type Intermediate = ();
+    type Value = u64;
+
+    fn start(&self) -> Self::Intermediate {}
+
+    fn end(&self, _: Self::Intermediate) -> Self::Value {
+        1
+    }
+
+    fn add(&self, v1: &Self::Value, v2: &Self::Value) -> Self::Value {
+        v1 + v2
+    }
+
+    fn zero(&self) -> Self::Value {
+        0
+    }
+
+    fn to_f64(&self, value: &Self::Value) -> f64 {
+        *value as f64
+    }
+
+    fn formatter(&self) -> &dyn ValueFormatter {
+        &CallsFormatter
+    }
+}
+
+impl ExperimentMeasurement for Calls {
+    fn create() -> Self {
+        Calls
+    }
+}
+
+#[test]
+fn measurement_unit_wall_time() {
+    assert_eq!(WallTime::unit(), "ns");
+    assert_eq!(time_title::<WallTime>(), "Time (ns)");
+}
+
+#[test]
+fn measurement_unit_custom() {
+    assert_eq!(Calls::unit(), "calls");
+    assert_eq!(time_title::<Calls>(), "Time (calls)");
+}
@@ -0,0 +1,153 @@
+use crate::Experiment;
+use crate::debug_diff;
+use crate::experiment_sealed::ExperimentSealed;
+use crate::mismatch::write_mismatch_files;
+use crate::record::record_treatment;
+use crate::tests::fixtures::{Len, Sum};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+/// Increments the last element of the input, while the expected output is the input itself.
+struct OffByOneExperiment;
+
+impl Experiment for OffByOneExperiment {
+    type InputFactors = Len;
+
+    type AlgFactors = Sum;
+
+    type Input = Vec<usize>;
+
+    type Output = Vec<usize>;
+
+    fn input(&mut self, input_variant: &Self::InputFactors) -> Self::Input {
+        (0..input_variant.0).collect()
+    }
+
+    fn execute(&mut self, _: &Self::AlgFactors, input: &Self::Input) -> Self::Output {
+        let mut output = input.clone();
+        if let Some(x) = output.last_mut() {
+            *x += 1;
+        }
+        output
+    }
+
+    fn expected_output(&self, _: &Self::InputFactors, input: &Self::Input) -> Option<Self::Output> {
+        Some(input.clone())
+    }
+}
+
+#[test]
+fn debug_diff_sequence() {
+    let expected: Vec<usize> = (0..10_000).collect();
+    let mut actual = expected.clone();
+    actual[1234] = 0;
+
+    let diff = debug_diff(&expected, &actual);
+
+    assert!(diff.starts_with("first difference at line 1236 (element at index 1234)"));
+    assert!(diff.contains("expected: 10002 lines, actual: 10002 lines"));
+    assert!(diff.contains(">   1236 |     1234,"));
+    assert!(diff.contains(">   1236 |     0,"));
+    assert!(diff.contains("    1233 |     1231,"));
+    assert!(!diff.contains("1230,"));
+    assert!(!diff.contains("1238,"));
+}
+
+#[test]
+fn debug_diff_different_lengths() {
+    let expected: Vec<usize> = (0..100).collect();
+    let actual: Vec<usize> = (0..98).collect();
+
+    let diff = debug_diff(&expected, &actual);
+
+    assert!(diff.starts_with("first difference at line 100 (element at index 98)"));
+    assert!(diff.contains("expected: 102 lines, actual: 100 lines"));
+    assert!(diff.contains(">    100 |     98,"));
+    assert!(diff.contains(">    100 | ]"));
+}
+
+#[test]
+fn debug_diff_nested_elements() {
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    let expected: Vec<_> = (0..5).map(|i| Point { x: i, y: i }).collect();
+    let actual: Vec<_> = (0..5).map(|i| Point { x: i, y: i % 3 }).collect();
+
+    let diff = debug_diff(&expected, &actual);
+
+    assert!(diff.starts_with("first difference at line 16 (element at index 3)"));
+}
+
+#[test]
+fn debug_diff_long_lines_are_truncated() {
+    let expected = "a".repeat(1000);
+    let actual = "b".repeat(1000);
+
+    let diff = debug_diff(&expected, &actual);
+
+    assert!(diff.starts_with("first difference at line 1\n"));
+    assert!(diff.len() < 500);
+}
+
+#[test]
+fn mismatch_writes_full_outputs() {
+    let name = "orx_criterion_test_mismatch";
+    let mut exp = OffByOneExperiment;
+    let (len, alg) = (Len(3), Sum);
+    let expected_path = exp.mismatch_path(name, &len, 0, &alg, "expected");
+    let actual_path = exp.mismatch_path(name, &len, 0, &alg, "actual");
+    let _ = std::fs::remove_file(&expected_path);
+    let _ = std::fs::remove_file(&actual_path);
+
+    let input = exp.input(&len);
+    let recorded = catch_unwind(AssertUnwindSafe(|| {
+        record_treatment(&mut exp, name, &len, 0, &alg, &input)
+    }));
+    let message = recorded
+        .err()
+        .and_then(|x| x.downcast::<String>().ok())
+        .expect("mismatch panics with a message");
+    assert!(message.contains("Full outputs are written to"));
+
+    let expected = std::fs::read_to_string(&expected_path).expect("expected output is written");
+    let actual = std::fs::read_to_string(&actual_path).expect("actual output is written");
+    assert_eq!(expected, "[\n    0,\n    1,\n    2,\n]");
+    assert_eq!(actual, "[\n    0,\n    1,\n    3,\n]");
+
+    let _ = std::fs::remove_file(&expected_path);
+    let _ = std::fs::remove_file(&actual_path);
+}
+
+#[test]
+fn debug_diff_identical_debug_representations() {
+    let diff = debug_diff(&vec![1.0, f64::NAN], &vec![1.0, f64::NAN]);
+
+    assert!(
+        diff.starts_with("debug representations of the expected and actual values are identical")
+    );
+}
+
+#[test]
+fn mismatch_files_note_identical_debug_representations() {
+    let name = "orx_criterion_test_mismatch_identical";
+    let (len, alg) = (Len(3), Sum);
+
+    let message =
+        write_mismatch_files(&OffByOneExperiment, name, &len, 0, &alg, &vec![1], &vec![1])
+            .expect("outputs are written");
+
+    assert!(message.starts_with("Full outputs are written to"));
+    assert!(message.contains(
+        "Note that the debug representations of the expected and actual values are identical"
+    ));
+    let expected_path = OffByOneExperiment.mismatch_path(name, &len, 0, &alg, "expected");
+    assert_eq!(
+        expected_path.file_name().and_then(|x| x.to_str()),
+        Some("l_3_alg_sum_expected.txt")
+    );
+    let _ = std::fs::remove_dir_all(OffByOneExperiment.experiment_dir(name));
+}
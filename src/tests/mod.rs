@@ -1,4 +1,31 @@
+mod alloc;
+mod cache;
+mod config;
+mod csv;
 mod data;
+mod error;
+mod estimate;
 mod experiment;
+mod experiment_with_approx_output;
+mod experiment_with_consumed_input;
 mod experiment_with_expected_output;
+mod fixtures;
+mod hooks;
+mod measurement;
+mod mismatch;
+mod order;
+mod pareto;
+mod phases;
+mod pivot;
+mod plan;
+mod record;
+mod registration;
+mod replicate;
+mod report;
+mod result;
+mod seed;
+mod summary_json;
+mod throughput;
+mod time_limits;
+mod tolerance;
 mod variant;
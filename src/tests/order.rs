@@ -0,0 +1,103 @@
+use crate::TreatmentOrder;
+use crate::experiment_sealed::ExperimentSealed;
+use crate::order::create_order_csv;
+use crate::tests::fixtures::{Len, Sum, SumExperiment};
+
+#[test]
+fn order_canonical() {
+    let order = TreatmentOrder::Canonical.execution_order(2, 3);
+    assert_eq!(order, [(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (1, 2)]);
+}
+
+#[test]
+fn order_randomized() {
+    let order = TreatmentOrder::Randomized { seed: 42 }.execution_order(3, 4);
+
+    let mut sorted = order.clone();
+    sorted.sort();
+    assert_eq!(sorted, TreatmentOrder::Canonical.execution_order(3, 4));
+    assert_ne!(order, sorted);
+
+    // same seed, same order
+    assert_eq!(
+        order,
+        TreatmentOrder::Randomized { seed: 42 }.execution_order(3, 4)
+    );
+    assert_ne!(
+        order,
+        TreatmentOrder::Randomized { seed: 7 }.execution_order(3, 4)
+    );
+}
+
+#[test]
+fn order_blocked_randomized() {
+    let order = TreatmentOrder::BlockedRandomized { seed: 42 }.execution_order(3, 4);
+
+    // each input is executed in a contiguous block
+    for block in order.chunks(4) {
+        let i = block[0].0;
+        assert!(block.iter().all(|x| x.0 == i));
+        let mut variants: Vec<_> = block.iter().map(|x| x.1).collect();
+        variants.sort();
+        assert_eq!(variants, [0, 1, 2, 3]);
+    }
+
+    let mut sorted = order.clone();
+    sorted.sort();
+    assert_eq!(sorted, TreatmentOrder::Canonical.execution_order(3, 4));
+    assert_ne!(order, sorted);
+}
+
+#[test]
+fn order_csv_starts_with_header() {
+    let name = "orx_criterion_test_order_csv";
+    let input_levels = [Len(10), Len(20)];
+    let alg_levels = [Sum];
+    let order = [(1, 0), (0, 0)];
+
+    create_order_csv(
+        &SumExperiment,
+        name,
+        &input_levels,
+        &alg_levels,
+        TreatmentOrder::Canonical,
+        &order,
+    )
+    .expect("order csv is created");
+
+    let path = SumExperiment.order_csv_path(name);
+    let csv = std::fs::read_to_string(&path).expect("order csv is readable");
+    let expected = "\
+order,t,i,r,a,treatment,seed
+1,2,2,1,1,l:20/alg:sum,NA
+2,1,1,1,1,l:10/alg:sum,NA
+";
+    assert_eq!(csv, expected);
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn order_csv_records_seed() {
+    let name = "orx_criterion_test_order_csv_seed";
+    let input_levels = [Len(10), Len(20)];
+    let alg_levels = [Sum];
+    let treatment_order = TreatmentOrder::Randomized { seed: 42 };
+    let order = treatment_order.execution_order(2, 1);
+
+    create_order_csv(
+        &SumExperiment,
+        name,
+        &input_levels,
+        &alg_levels,
+        treatment_order,
+        &order,
+    )
+    .expect("order csv is created");
+
+    let path = SumExperiment.order_csv_path(name);
+    let csv = std::fs::read_to_string(&path).expect("order csv is readable");
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("order,t,i,r,a,treatment,seed"));
+    assert!(lines.all(|x| x.ends_with(",42")));
+    let _ = std::fs::remove_file(path);
+}
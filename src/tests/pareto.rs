@@ -0,0 +1,98 @@
+use crate::experiment_sealed::ExperimentSealed;
+use crate::pareto::{ParetoFronts, dominates, pareto_front, pareto_front_across_inputs};
+use crate::record::TreatmentRecord;
+use crate::summary::create_pareto_csv;
+use crate::tests::fixtures::{ALGS, ChunkedSum, INPUTS, parse_csv};
+use criterion::measurement::WallTime;
+
+#[test]
+fn dominance() {
+    assert!(dominates(&[1.0, 2.0], &[2.0, 2.0]));
+    assert!(dominates(&[1.0, 1.0], &[2.0, 2.0]));
+    assert!(!dominates(&[1.0, 2.0], &[1.0, 2.0]));
+    assert!(!dominates(&[1.0, 3.0], &[2.0, 2.0]));
+}
+
+#[test]
+fn pareto_front_of_single_input() {
+    let points = vec![
+        Some(vec![10.0, 5.0]),
+        Some(vec![20.0, 1.0]),
+        Some(vec![30.0, 5.0]),
+        None,
+        Some(vec![10.0, 5.0]),
+    ];
+    assert_eq!(pareto_front(&points), [true, true, false, false, true]);
+}
+
+#[test]
+fn pareto_front_of_all_inputs() {
+    let points = vec![
+        vec![
+            Some(vec![10.0, 5.0]),
+            Some(vec![20.0, 1.0]),
+            Some(vec![30.0, 5.0]),
+            Some(vec![1.0, 1.0]),
+        ],
+        vec![
+            Some(vec![100.0, 5.0]),
+            Some(vec![200.0, 1.0]),
+            Some(vec![100.0, 5.0]),
+            None,
+        ],
+    ];
+    assert_eq!(
+        pareto_front_across_inputs(&points),
+        [true, true, false, false]
+    );
+    assert_eq!(pareto_front_across_inputs(&[]), Vec::<bool>::new());
+}
+
+#[test]
+fn pareto_csv_labels_per_input_dominance() {
+    let name = "orx_criterion_test_pareto_csv";
+    let estimates = vec![
+        vec![Some(10.0), Some(20.0), Some(30.0)],
+        vec![Some(300.0), Some(200.0), Some(100.0)],
+    ];
+    let record = TreatmentRecord {
+        metrics: vec![("cost", 1.0)],
+        ..Default::default()
+    };
+    let records = vec![vec![record; 3]; 2];
+    let fronts = ParetoFronts::new(&ChunkedSum, &["cost"], &estimates, &records);
+
+    create_pareto_csv::<_, WallTime>(
+        &ChunkedSum,
+        name,
+        &INPUTS,
+        &ALGS,
+        &estimates,
+        &records,
+        &fronts,
+    )
+    .expect("pareto csv is created");
+
+    let path = ChunkedSum.pareto_csv_path(name);
+    let csv = std::fs::read_to_string(&path).expect("pareto csv is readable");
+    let rows = parse_csv(&csv, ',');
+    assert_eq!(
+        rows[0],
+        [
+            "scope",
+            "dominance",
+            "i",
+            "a",
+            "len",
+            "chunk",
+            "Time (ns)",
+            "cost"
+        ]
+    );
+    // chunk 4 is not dominated on every input, although chunk 64 has a smaller mean time
+    let overall: Vec<_> = rows[1..].iter().filter(|x| x[0] == "all").collect();
+    assert_eq!(overall.len(), 3);
+    assert!(overall.iter().all(|x| x[1] == "per_input"));
+    assert_eq!(overall[1][5..], ["4", "110", "1"]);
+    let _ = std::fs::remove_file(path);
+}
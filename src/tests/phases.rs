@@ -0,0 +1,109 @@
+use crate::tests::fixtures::{Len, Sum, quick_criterion, read_summary_csv, summary_cell};
+use crate::{Experiment, PhaseTimer};
+use std::hint::black_box;
+use std::time::Duration;
+
+/// Sums up the input twice, where only the first sum is a phase of the execution.
+struct PhasedExperiment;
+
+impl Experiment for PhasedExperiment {
+    type InputFactors = Len;
+
+    type AlgFactors = Sum;
+
+    type Input = Vec<usize>;
+
+    type Output = usize;
+
+    fn input(&mut self, input_variant: &Self::InputFactors) -> Self::Input {
+        (0..input_variant.0).collect()
+    }
+
+    fn execute(&mut self, alg_variant: &Self::AlgFactors, input: &Self::Input) -> Self::Output {
+        self.execute_phased(alg_variant, input, &mut PhaseTimer::default())
+    }
+
+    fn execute_phased(
+        &mut self,
+        _: &Self::AlgFactors,
+        input: &Self::Input,
+        timer: &mut PhaseTimer,
+    ) -> Self::Output {
+        timer.start("sum");
+        let sum = input.iter().sum();
+        timer.stop();
+        black_box(input.iter().sum::<usize>());
+        sum
+    }
+
+    fn phases(&self) -> Vec<&'static str> {
+        vec!["sum"]
+    }
+}
+
+#[test]
+fn phase_timer_declared_and_new_phases() {
+    let mut timer = PhaseTimer::new(vec!["build", "query"]);
+
+    timer.start("query");
+    std::thread::sleep(Duration::from_millis(2));
+    timer.start("cleanup");
+    timer.end_execution();
+
+    let names: Vec<_> = timer.durations().iter().map(|x| x.0).collect();
+    assert_eq!(names, ["build", "query", "cleanup"]);
+    assert_eq!(timer.durations()[0].1, Duration::ZERO);
+    assert!(timer.durations()[1].1 >= Duration::from_millis(2));
+}
+
+#[test]
+fn default_phase_timer_is_disabled() {
+    let mut timer = PhaseTimer::default();
+
+    timer.start("build");
+    std::thread::sleep(Duration::from_millis(1));
+    timer.end_execution();
+
+    assert!(timer.durations().is_empty());
+    assert!(timer.mean_durations().is_empty());
+}
+
+#[test]
+fn phase_timer_mean_durations() {
+    let mut timer = PhaseTimer::new(vec!["a"]);
+    assert!(timer.mean_durations().is_empty());
+
+    for _ in 0..4 {
+        timer.start("a");
+        std::thread::sleep(Duration::from_millis(1));
+        timer.end_execution();
+    }
+
+    let means = timer.mean_durations();
+    assert_eq!(means.len(), 1);
+    assert_eq!(means[0].0, "a");
+    assert!(means[0].1 >= 1_000_000.0);
+
+    // stopping without a current phase does nothing
+    timer.stop();
+    assert_eq!(timer.mean_durations(), means);
+}
+
+#[test]
+fn phase_times_are_within_measured_time() {
+    let name = "orx_criterion_test_phases";
+    let mut c = quick_criterion();
+    PhasedExperiment
+        .try_bench(&mut c, name, &[Len(10_000)], &[Sum])
+        .expect("experiment is reported");
+
+    let summary = read_summary_csv(name);
+    let time: f64 = summary_cell(&summary, 1, "Time (ns)")
+        .parse()
+        .expect("time is measured");
+    let phase: f64 = summary_cell(&summary, 1, "sum (ns)")
+        .parse()
+        .expect("phase is measured");
+    assert!(phase > 0.0);
+    assert!(phase < time);
+}
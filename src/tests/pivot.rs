@@ -0,0 +1,57 @@
+use crate::experiment_sealed::ExperimentSealed;
+use crate::pivot::{create_pivot_csv, pivot_titles, score_ranks};
+use crate::record::TreatmentRecord;
+use crate::tests::fixtures::{Chunk, ChunkedSum, Len, chunked_sum_result};
+use crate::{ExperimentResult, SummaryLayout};
+
+#[test]
+fn summary_layouts() {
+    assert_eq!(SummaryLayout::default(), SummaryLayout::Table);
+    assert!(SummaryLayout::Table.has_table() && !SummaryLayout::Table.has_pivot());
+    assert!(!SummaryLayout::Pivot.has_table() && SummaryLayout::Pivot.has_pivot());
+    assert!(SummaryLayout::Both.has_table() && SummaryLayout::Both.has_pivot());
+}
+
+#[test]
+fn pivot_columns_are_variants() {
+    let inputs = [Len(10), Len(100)];
+    let algs = [Chunk(1), Chunk(8)];
+    let estimates = vec![vec![None; 2]; 2];
+    let records = vec![vec![TreatmentRecord::default(); 2]; 2];
+    let result =
+        ExperimentResult::<ChunkedSum>::new("chunks", "ns", &inputs, &algs, &estimates, &records);
+
+    assert_eq!(pivot_titles(&result), ["i", "len", "c:1", "c:8"]);
+}
+
+#[test]
+fn rank_relative_scores() {
+    assert_eq!(
+        score_ranks(&[Some(1.5), None, Some(1.0), Some(2.0)]),
+        [Some(2), None, Some(1), Some(3)]
+    );
+    assert_eq!(score_ranks(&[None, None]), [None, None]);
+}
+
+#[test]
+fn pivot_csv_starts_with_header() {
+    let estimates = vec![
+        vec![Some(10.0), Some(20.0), Some(40.0)],
+        vec![Some(400.0), Some(100.0), None],
+    ];
+    let records = vec![vec![TreatmentRecord::default(); 3]; 2];
+    let result = chunked_sum_result(&estimates, &records);
+
+    create_pivot_csv(&ChunkedSum, &result).expect("pivot csv is created");
+
+    let path = ChunkedSum.pivot_csv_path(&result.name);
+    let csv = std::fs::read_to_string(&path).expect("pivot csv is readable");
+    let expected = "\
+i,len,c:1,c:4,c:64
+1,10,10,20,40
+2,1000,400,100,NA
+Relative (geo mean),,2.00,1.41,NA
+";
+    assert_eq!(csv, expected);
+    let _ = std::fs::remove_file(path);
+}
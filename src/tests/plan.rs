@@ -0,0 +1,194 @@
+use crate::plan::format_duration;
+use crate::tests::fixtures::Len;
+use crate::{Experiment, Factors, TreatmentConfig};
+use std::time::Duration;
+
+struct Reverse(bool);
+
+impl Factors for Reverse {
+    fn factor_names() -> Vec<&'static str> {
+        vec!["reverse"]
+    }
+
+    fn factor_names_short() -> Vec<&'static str> {
+        vec!["r"]
+    }
+
+    fn factor_levels(&self) -> Vec<String> {
+        vec![self.0.to_string()]
+    }
+
+    fn factor_levels_short(&self) -> Vec<String> {
+        vec![
+            match self.0 {
+                true => "T",
+                false => "F",
+            }
+            .to_string(),
+        ]
+    }
+}
+
+struct PlanExperiment;
+
+impl Experiment for PlanExperiment {
+    type InputFactors = Len;
+
+    type AlgFactors = Reverse;
+
+    type Input = Vec<usize>;
+
+    type Output = Vec<usize>;
+
+    fn input(&mut self, len: &Self::InputFactors) -> Self::Input {
+        (0..len.0).collect()
+    }
+
+    fn execute(&mut self, reverse: &Self::AlgFactors, input: &Self::Input) -> Self::Output {
+        match reverse.0 {
+            true => input.iter().rev().copied().collect(),
+            false => input.clone(),
+        }
+    }
+
+    fn configure(&self, len: &Self::InputFactors, _: &Self::AlgFactors) -> TreatmentConfig {
+        match len.0 {
+            1000 => TreatmentConfig {
+                measurement_time: Some(Duration::from_secs(20)),
+                ..Default::default()
+            },
+            _ => TreatmentConfig::default(),
+        }
+    }
+
+    fn time_budget(&self) -> Option<Duration> {
+        Some(Duration::from_secs(60))
+    }
+}
+
+#[test]
+fn plan_lists_treatments() {
+    let input_levels = [Len(10), Len(1000), Len(100)];
+    let alg_levels = [Reverse(false), Reverse(true)];
+
+    let plan = PlanExperiment.plan("plan", &input_levels, &alg_levels);
+
+    assert_eq!(plan.name, "plan");
+    assert_eq!(plan.input_times.len(), 3);
+    assert_eq!(plan.treatments.len(), 6);
+    assert_eq!(plan.time_budget, Some(Duration::from_secs(60)));
+
+    let t = &plan.treatments[3];
+    assert_eq!((t.t, t.i, t.a), (4, 2, 2));
+    assert_eq!(t.key_long, "len:1000/reverse:true");
+    assert_eq!(t.key_short, "l:1000/r:T");
+    assert_eq!(t.warm_up_time, Duration::from_secs(3));
+}
+
+#[test]
+fn plan_uses_treatment_config() {
+    let input_levels = [Len(10), Len(1000), Len(100)];
+    let alg_levels = [Reverse(false)];
+
+    let plan = PlanExperiment.plan("plan", &input_levels, &alg_levels);
+
+    let measurement_times: Vec<_> = plan
+        .treatments
+        .iter()
+        .map(|x| x.measurement_time.as_secs())
+        .collect();
+    // criterion keeps the measurement time set for a previous treatment
+    assert_eq!(measurement_times, [5, 20, 20]);
+
+    assert!(plan.estimated_duration() >= Duration::from_secs(3 * 3 + 5 + 20 + 20));
+}
+
+#[test]
+fn plan_format_duration() {
+    assert_eq!(format_duration(Duration::from_millis(850)), "850ms");
+    assert_eq!(format_duration(Duration::from_millis(8500)), "8.5s");
+    assert_eq!(format_duration(Duration::from_secs(125)), "2m 05s");
+    assert_eq!(format_duration(Duration::from_secs(3725)), "1h 02m 05s");
+}
+
+#[derive(Default)]
+struct HookedPlanExperiment {
+    calls: Vec<String>,
+    time_inputs: bool,
+}
+
+impl Experiment for HookedPlanExperiment {
+    type InputFactors = Len;
+
+    type AlgFactors = Reverse;
+
+    type Input = Vec<usize>;
+
+    type Output = Vec<usize>;
+
+    fn input(&mut self, len: &Self::InputFactors) -> Self::Input {
+        self.calls.push(format!("input {}", len.0));
+        (0..len.0).collect()
+    }
+
+    fn execute(&mut self, _: &Self::AlgFactors, input: &Self::Input) -> Self::Output {
+        input.clone()
+    }
+
+    fn plan_input_times(&self) -> bool {
+        self.time_inputs
+    }
+
+    fn before_input(&mut self, len: &Self::InputFactors) -> Option<String> {
+        self.calls.push(format!("before_input {}", len.0));
+        None
+    }
+
+    fn after_input(&mut self, len: &Self::InputFactors) -> Option<String> {
+        self.calls.push(format!("after_input {}", len.0));
+        None
+    }
+
+    fn before_treatment(&mut self, _: &Self::InputFactors, _: &Self::AlgFactors) -> Option<String> {
+        self.calls.push("before_treatment".to_string());
+        None
+    }
+}
+
+#[test]
+fn plan_calls_input_hooks() {
+    let mut exp = HookedPlanExperiment {
+        time_inputs: true,
+        ..Default::default()
+    };
+
+    let plan = exp.plan(
+        "plan",
+        &[Len(10), Len(20)],
+        &[Reverse(false), Reverse(true)],
+    );
+
+    assert_eq!(
+        exp.calls,
+        [
+            "before_input 10",
+            "input 10",
+            "after_input 10",
+            "before_input 20",
+            "input 20",
+            "after_input 20",
+        ]
+    );
+    assert!(plan.input_times.iter().all(Option::is_some));
+}
+
+#[test]
+fn plan_does_not_create_inputs_by_default() {
+    let mut exp = HookedPlanExperiment::default();
+
+    let plan = exp.plan("plan", &[Len(10), Len(20)], &[Reverse(false)]);
+
+    assert!(exp.calls.is_empty());
+    assert_eq!(plan.input_times, [None, None]);
+    assert_eq!(plan.estimated_duration(), Duration::from_secs(2 * (3 + 5)));
+}
@@ -0,0 +1,64 @@
+use crate::record::{
+    InputRecord, TreatmentRecord, TreatmentStatus, format_metric, format_time, has_input_sizes,
+    input_columns, input_titles, metric_names,
+};
+use std::time::Duration;
+
+#[test]
+fn metric_names_in_order_of_appearance() {
+    let record = |metrics: Vec<(&'static str, f64)>| TreatmentRecord {
+        metrics,
+        ..Default::default()
+    };
+    let records = vec![
+        vec![record(vec![]), record(vec![("cost", 1.0)])],
+        vec![
+            record(vec![("gap", 0.1), ("cost", 2.0)]),
+            record(vec![("cost", 3.0), ("iterations", 7.0)]),
+        ],
+    ];
+
+    assert_eq!(metric_names(&records), ["cost", "gap", "iterations"]);
+    assert_eq!(records[1][0].metric("gap"), Some(0.1));
+    assert_eq!(records[0][0].metric("gap"), None);
+}
+
+#[test]
+fn format_metric_values() {
+    assert_eq!(format_metric(Some(42.0)), "42");
+    assert_eq!(format_metric(Some(-3.0)), "-3");
+    assert_eq!(format_metric(Some(0.125)), "0.1250");
+    assert_eq!(format_metric(None), "NA");
+}
+
+#[test]
+fn format_time_of_treatments() {
+    assert_eq!(format_time(TreatmentStatus::Measured, Some(1234.4)), "1234");
+    assert_eq!(format_time(TreatmentStatus::Measured, None), "NA");
+    assert_eq!(format_time(TreatmentStatus::TimedOut, None), "TIMEOUT");
+    assert_eq!(format_time(TreatmentStatus::Skipped, Some(1.0)), "SKIPPED");
+    assert_eq!(TreatmentRecord::skipped().status, TreatmentStatus::Skipped);
+}
+
+#[test]
+fn input_columns_of_treatments() {
+    let input = InputRecord {
+        elapsed: Duration::from_micros(1500),
+        size_bytes: Some(800),
+    };
+    let record = |input| TreatmentRecord {
+        input,
+        ..Default::default()
+    };
+
+    assert!(has_input_sizes(&[vec![record(None), record(Some(input))]]));
+    assert!(!has_input_sizes(&[vec![record(None)]]));
+
+    assert_eq!(
+        input_titles(true),
+        ["Input Time (ms)", "Input Size (bytes)"]
+    );
+    assert_eq!(input_columns(Some(input), true), ["1.500", "800"]);
+    assert_eq!(input_columns(Some(input), false), ["1.500"]);
+    assert_eq!(input_columns(None, true), ["NA", "NA"]);
+}
@@ -0,0 +1,290 @@
+use crate::experiment_sealed::ExperimentSealed;
+use crate::registration::filename_safe;
+use crate::tests::fixtures::quick_criterion;
+use crate::{Error, Experiment, Factors, Registration, TreatmentOrder};
+use std::path::PathBuf;
+
+struct Settings {
+    len: usize,
+    position: &'static str,
+}
+
+impl Factors for Settings {
+    fn factor_names() -> Vec<&'static str> {
+        vec!["len", "position"]
+    }
+
+    fn factor_names_short() -> Vec<&'static str> {
+        vec!["l", "p"]
+    }
+
+    fn factor_levels(&self) -> Vec<String> {
+        vec![self.len.to_string(), self.position.to_string()]
+    }
+}
+
+struct Search(&'static str);
+
+impl Factors for Search {
+    fn factor_names() -> Vec<&'static str> {
+        vec!["alg"]
+    }
+
+    fn factor_levels(&self) -> Vec<String> {
+        vec![self.0.to_string()]
+    }
+}
+
+struct SearchExperiment {
+    registration: Registration,
+    num_replicates: usize,
+}
+
+impl Experiment for SearchExperiment {
+    type InputFactors = Settings;
+
+    type AlgFactors = Search;
+
+    type Input = Vec<usize>;
+
+    type Output = bool;
+
+    fn input(&mut self, input_variant: &Self::InputFactors) -> Self::Input {
+        (0..input_variant.len).collect()
+    }
+
+    fn num_replicates(&self) -> usize {
+        self.num_replicates
+    }
+
+    fn registration(&self) -> Registration {
+        self.registration
+    }
+
+    fn treatment_order(&self) -> TreatmentOrder {
+        TreatmentOrder::Canonical
+    }
+
+    fn execute(&mut self, _: &Self::AlgFactors, input: &Self::Input) -> Self::Output {
+        input.contains(&0)
+    }
+}
+
+fn parameterized(num_replicates: usize) -> SearchExperiment {
+    SearchExperiment {
+        registration: Registration::Parameterized {
+            input_factor: "len",
+        },
+        num_replicates,
+    }
+}
+
+fn input_levels() -> [Settings; 4] {
+    [
+        Settings {
+            len: 8,
+            position: "mid",
+        },
+        Settings {
+            len: 8,
+            position: "none",
+        },
+        Settings {
+            len: 64,
+            position: "mid",
+        },
+        Settings {
+            len: 64,
+            position: "none",
+        },
+    ]
+}
+
+#[test]
+fn filename_safe_replaces_special_characters() {
+    assert_eq!(filename_safe("len:8/alg:sort"), "len_8_alg_sort");
+    assert_eq!(filename_safe("a?b\"c\\d*e<f>g|h^i"), "a_b_c_d_e_f_g_h_i");
+}
+
+#[test]
+fn filename_safe_truncates_long_names() {
+    let name = "x".repeat(100);
+    assert_eq!(filename_safe(&name), "x".repeat(64));
+
+    // truncated at a char boundary
+    let name = format!("{}é", "x".repeat(63));
+    assert_eq!(filename_safe(&name), "x".repeat(63));
+}
+
+#[test]
+fn flat_registration() {
+    let exp = SearchExperiment {
+        registration: Registration::Flat,
+        num_replicates: 1,
+    };
+    let input = Settings {
+        len: 8,
+        position: "mid",
+    };
+
+    assert_eq!(exp.benchmark_group_id("search", &input, 0), "search");
+    assert_eq!(exp.benchmark_parameter(&input), None);
+    assert_eq!(
+        exp.run_estimates_path("search", &input, 0, &Search("linear")),
+        [
+            "target",
+            "criterion",
+            "search",
+            "l_8_p_mid_alg_linear",
+            "new",
+            "estimates.json"
+        ]
+        .iter()
+        .collect::<PathBuf>()
+    );
+}
+
+#[test]
+fn parameterized_registration() {
+    let exp = parameterized(1);
+    let input = Settings {
+        len: 8,
+        position: "mid",
+    };
+
+    assert_eq!(exp.benchmark_group_id("search", &input, 0), "search/p:mid");
+    assert_eq!(exp.benchmark_parameter(&input), Some("8".to_string()));
+    assert_eq!(
+        exp.run_estimates_path("search", &input, 0, &Search("linear")),
+        [
+            "target",
+            "criterion",
+            "search_p_mid",
+            "alg_linear",
+            "8",
+            "new",
+            "estimates.json"
+        ]
+        .iter()
+        .collect::<PathBuf>()
+    );
+}
+
+#[test]
+fn parameterized_registration_with_replicates() {
+    let exp = parameterized(2);
+    let input = Settings {
+        len: 8,
+        position: "mid",
+    };
+
+    assert_eq!(
+        exp.benchmark_group_id("search", &input, 1),
+        "search/p:mid/r:2"
+    );
+}
+
+#[test]
+fn parameterized_execution_order_groups_treatments() {
+    let input_levels = input_levels();
+    let alg_levels = [Search("linear"), Search("binary")];
+
+    let exp = SearchExperiment {
+        registration: Registration::Flat,
+        num_replicates: 1,
+    };
+    let order = exp.execution_order("search", &input_levels, &alg_levels);
+    let canonical = [
+        (0, 0),
+        (0, 1),
+        (1, 0),
+        (1, 1),
+        (2, 0),
+        (2, 1),
+        (3, 0),
+        (3, 1),
+    ];
+    assert_eq!(order, canonical);
+
+    let exp = parameterized(1);
+    let order = exp.execution_order("search", &input_levels, &alg_levels);
+    let grouped = [
+        (0, 0),
+        (0, 1),
+        (2, 0),
+        (2, 1),
+        (1, 0),
+        (1, 1),
+        (3, 0),
+        (3, 1),
+    ];
+    assert_eq!(order, grouped);
+}
+
+#[test]
+fn unknown_input_factor_is_rejected() {
+    let mut exp = SearchExperiment {
+        registration: Registration::Parameterized {
+            input_factor: "size",
+        },
+        num_replicates: 1,
+    };
+    let input_levels = input_levels();
+    let alg_levels = [Search("linear")];
+    let mut c = quick_criterion();
+
+    let result = exp.try_bench(
+        &mut c,
+        "orx_criterion_test_unknown_input_factor",
+        &input_levels,
+        &alg_levels,
+    );
+    let Err(error) = result else {
+        unreachable!("unknown input factor must be rejected");
+    };
+    assert!(matches!(
+        error,
+        Error::UnknownInputFactor {
+            input_factor: "size",
+            ..
+        }
+    ));
+    assert_eq!(
+        error.to_string(),
+        "input factor \"size\" of the parameterized registration is not one of the input factors [\"len\", \"position\"]"
+    );
+}
+
+#[test]
+fn summary_files_are_in_the_benchmark_directory() {
+    let exp = SearchExperiment {
+        registration: Registration::Flat,
+        num_replicates: 1,
+    };
+    let input = Settings {
+        len: 8,
+        position: "mid",
+    };
+    let name = "search:v2";
+
+    let bench_dir = exp
+        .run_estimates_path(name, &input, 0, &Search("linear"))
+        .ancestors()
+        .nth(3)
+        .map(PathBuf::from);
+    let expected: PathBuf = ["target", "criterion", "search_v2"].iter().collect();
+    assert_eq!(bench_dir, Some(expected.clone()));
+
+    assert_eq!(
+        exp.summary_csv_path(name),
+        expected.join("summary_search_v2.csv")
+    );
+    assert_eq!(exp.report_path(name), expected.join("report_search_v2.md"));
+    assert_eq!(
+        exp.mismatch_path(name, &input, 0, &Search("linear"), "output")
+            .parent()
+            .and_then(|x| x.parent())
+            .map(PathBuf::from),
+        Some(expected)
+    );
+}
@@ -0,0 +1,181 @@
+use crate::record::{InputRecord, TreatmentRecord, TreatmentStatus};
+use crate::replicate::{Replicate, ReplicateStats, aggregate_replicates, replicate_columns};
+use crate::tests::fixtures::{Len, Sum, quick_criterion};
+use crate::{AllocationStats, Error, Experiment};
+use std::time::Duration;
+
+fn record(cost: f64, count: usize) -> TreatmentRecord {
+    TreatmentRecord {
+        metrics: vec![("cost", cost)],
+        allocations: Some(AllocationStats {
+            count,
+            bytes: 8 * count,
+            peak_bytes: 8,
+        }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn replicate_seeds() {
+    let seeds: Vec<_> = (0..4).map(|r| Replicate::new(42, r).seed).collect();
+    assert_eq!(seeds[0], 42);
+    assert_eq!(
+        seeds,
+        (0..4)
+            .map(|r| Replicate::new(42, r).seed)
+            .collect::<Vec<_>>()
+    );
+    for (i, x) in seeds.iter().enumerate() {
+        assert!(!seeds[i + 1..].contains(x));
+    }
+    assert_ne!(seeds[1], Replicate::new(7, 1).seed);
+}
+
+#[test]
+fn replicate_stats() {
+    let stats = ReplicateStats::new(&[10.0, 30.0, 20.0]).expect("has values");
+    assert_eq!(
+        stats,
+        ReplicateStats {
+            count: 3,
+            mean: 20.0,
+            min: 10.0,
+            max: 30.0
+        }
+    );
+    assert_eq!(format!("{:.2}", stats.spread()), "100.00");
+    assert_eq!(replicate_columns(Some(stats)), ["3", "10", "30", "100.00"]);
+
+    assert_eq!(ReplicateStats::new(&[]), None);
+    assert_eq!(replicate_columns(None), ["NA", "NA", "NA", "NA"]);
+}
+
+#[test]
+fn aggregate_single_replicate() {
+    let estimates = vec![vec![Some(1.0), None]];
+    let records = vec![vec![record(1.0, 1), record(2.0, 2)]];
+
+    let (estimates, records) = aggregate_replicates(1, estimates, records);
+
+    assert_eq!(estimates, [[Some(1.0), None]]);
+    assert_eq!(records[0][1].metric("cost"), Some(2.0));
+    assert!(records[0][0].replicates.is_none());
+}
+
+#[test]
+fn aggregate_multiple_replicates() {
+    // 2 inputs x 2 replicates, 2 variants
+    let estimates = vec![
+        vec![Some(10.0), Some(100.0)],
+        vec![Some(30.0), Some(200.0)],
+        vec![Some(5.0), None],
+        vec![Some(7.0), None],
+    ];
+    let mut timed_out = record(0.0, 0);
+    timed_out.status = TreatmentStatus::TimedOut;
+    let records = vec![
+        vec![record(1.0, 1), record(3.0, 2)],
+        vec![record(3.0, 3), record(5.0, 4)],
+        vec![record(1.0, 1), record(1.0, 1)],
+        vec![record(1.0, 1), timed_out],
+    ];
+
+    let (estimates, records) = aggregate_replicates(2, estimates, records);
+
+    assert_eq!(estimates, [[Some(20.0), Some(150.0)], [Some(6.0), None]]);
+
+    let first = &records[0][0];
+    assert_eq!(first.metric("cost"), Some(2.0));
+    assert_eq!(first.allocations.map(|x| x.count), Some(2));
+    let stats = first.replicates.expect("replicated");
+    assert_eq!((stats.count, stats.min, stats.max), (2, 10.0, 30.0));
+
+    assert_eq!(records[1][1].status, TreatmentStatus::TimedOut);
+    assert!(records[1][1].replicates.is_none());
+}
+
+#[test]
+fn aggregate_input_records() {
+    let record = |millis, size_bytes| TreatmentRecord {
+        input: Some(InputRecord {
+            elapsed: Duration::from_millis(millis),
+            size_bytes,
+        }),
+        ..Default::default()
+    };
+    let estimates = vec![vec![Some(1.0)]; 4];
+    let records = vec![
+        vec![record(10, Some(100))],
+        vec![record(30, Some(300))],
+        vec![record(5, Some(8))],
+        vec![record(7, None)],
+    ];
+
+    let (_, records) = aggregate_replicates(2, estimates, records);
+
+    let first = records[0][0].input.expect("created");
+    assert_eq!(first.elapsed, Duration::from_millis(20));
+    assert_eq!(first.size_bytes, Some(200));
+
+    let second = records[1][0].input.expect("created");
+    assert_eq!(second.elapsed, Duration::from_millis(6));
+    assert_eq!(second.size_bytes, None);
+}
+
+#[test]
+fn aggregate_partially_measured_replicates() {
+    // 1 input x 3 replicates, 1 variant, the estimate of the second replicate is missing
+    let estimates = vec![vec![Some(10.0)], vec![None], vec![Some(30.0)]];
+    let records = vec![vec![TreatmentRecord::default()]; 3];
+
+    let (estimates, records) = aggregate_replicates(3, estimates, records);
+
+    assert_eq!(estimates, [[Some(20.0)]]);
+    let stats = records[0][0].replicates.expect("replicated");
+    assert_eq!(stats.count, 2);
+    assert_eq!(replicate_columns(Some(stats)), ["2", "10", "30", "100.00"]);
+}
+
+struct NoReplicates;
+
+impl Experiment for NoReplicates {
+    type InputFactors = Len;
+
+    type AlgFactors = Sum;
+
+    type Input = Vec<usize>;
+
+    type Output = usize;
+
+    fn input(&mut self, input_variant: &Self::InputFactors) -> Self::Input {
+        (0..input_variant.0).collect()
+    }
+
+    fn execute(&mut self, _: &Self::AlgFactors, input: &Self::Input) -> Self::Output {
+        input.iter().sum()
+    }
+
+    fn num_replicates(&self) -> usize {
+        0
+    }
+}
+
+#[test]
+fn zero_replicates_are_rejected() {
+    let mut c = quick_criterion();
+    let result = NoReplicates.try_bench(
+        &mut c,
+        "orx_criterion_test_zero_replicates",
+        &[Len(4)],
+        &[Sum],
+    );
+    let Err(error) = result else {
+        unreachable!("experiment without replicates must fail");
+    };
+    assert!(matches!(error, Error::ZeroReplicates));
+    assert_eq!(
+        error.to_string(),
+        "number of replicates must be at least one"
+    );
+}
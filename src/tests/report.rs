@@ -0,0 +1,83 @@
+use crate::ExperimentResult;
+use crate::record::{TreatmentRecord, TreatmentStatus};
+use crate::report::{best_worst_table, md_cell, md_code, md_table, recommendation};
+use crate::tests::fixtures::{ChunkedSum, chunked_sum_result};
+
+fn result(estimates: Vec<Vec<Option<f64>>>) -> ExperimentResult<'static, ChunkedSum> {
+    let records: Vec<Vec<_>> = estimates
+        .iter()
+        .map(|x| {
+            x.iter()
+                .map(|x| match x {
+                    Some(_) => TreatmentRecord::default(),
+                    None => TreatmentRecord::skipped(),
+                })
+                .collect()
+        })
+        .collect();
+    chunked_sum_result(&estimates, &records)
+}
+
+#[test]
+fn escape_markdown_cells() {
+    assert_eq!(md_cell("len:10"), "len:10");
+    assert_eq!(md_cell("a|b"), "a\\|b");
+    assert_eq!(md_cell("a\\b\nc"), "a\\\\b c");
+
+    assert_eq!(md_code("len"), "`len`");
+    assert_eq!(md_code("a`b"), "``a`b``");
+    assert_eq!(md_code("a``b`c"), "```a``b`c```");
+    assert_eq!(md_code("`a`"), "`` `a` ``");
+    assert_eq!(md_cell(&md_code("a|`b")), "``a\\|`b``");
+}
+
+#[test]
+fn markdown_table() {
+    let rows = vec![vec!["1".to_string(), "(1|2)".to_string()]];
+    let table = md_table(&["i", "time"], &rows, |j| j == 1);
+    assert_eq!(table, "| i | time |\n| --- | ---: |\n| 1 | (1\\|2) |\n");
+}
+
+#[test]
+fn best_and_worst_per_input() {
+    let result = result(vec![
+        vec![Some(10.0), Some(20.0), Some(40.0)],
+        vec![Some(400.0), None, None],
+    ]);
+    let expected = "\
+| i | Input | Best | Best (ns) | Worst | Worst (ns) |
+| --- | --- | --- | ---: | --- | ---: |
+| 1 | len:10 | [a = 1] chunk:1 | 10 | [a = 3] chunk:64 | 40 |
+| 2 | len:1000 | [a = 1] chunk:1 | 400 | NA | NA |
+";
+    assert_eq!(best_worst_table(&result), expected);
+}
+
+#[test]
+fn recommend_best_overall() {
+    let result = result(vec![
+        vec![Some(10.0), Some(20.0), Some(40.0)],
+        vec![Some(400.0), Some(100.0), None],
+    ]);
+    let expected = "\
+**[a = 2] chunk:4** is recommended as the best algorithm variant across all inputs.
+
+Variants measured on all inputs are ranked by the geometric mean of their time relative to the fastest variant of each input:
+
+| Rank | a | Variant | Relative (geo mean) |
+| --- | --- | --- | ---: |
+| 1 | 2 | chunk:4 | 1.41 |
+| 2 | 1 | chunk:1 | 2.00 |
+";
+    assert_eq!(recommendation(&result), expected);
+}
+
+#[test]
+fn no_recommendation_without_complete_variants() {
+    let result = result(vec![vec![Some(10.0), None, None], vec![None, None, None]]);
+    assert_eq!(
+        recommendation(&result),
+        "None of the algorithm variants is measured on all inputs; hence, no variant is recommended.\n"
+    );
+    assert_eq!(result.treatments()[1].status, TreatmentStatus::Skipped);
+}
@@ -0,0 +1,90 @@
+use crate::record::{TreatmentRecord, TreatmentStatus};
+use crate::tests::fixtures::{ALGS, Chunk, ChunkedSum, INPUTS, Len, chunked_sum_result};
+use crate::{ExperimentResult, Validation};
+
+fn record(status: TreatmentStatus, validated: bool) -> TreatmentRecord {
+    TreatmentRecord {
+        status,
+        validated,
+        ..Default::default()
+    }
+}
+
+fn result() -> ExperimentResult<'static, ChunkedSum> {
+    use TreatmentStatus::*;
+    let estimates = vec![
+        vec![Some(10.0), Some(20.0), Some(40.0)],
+        vec![Some(400.0), Some(200.0), None],
+    ];
+    let records = vec![
+        vec![
+            record(Measured, true),
+            record(Measured, true),
+            record(Measured, false),
+        ],
+        vec![
+            record(Measured, true),
+            record(Measured, true),
+            record(Skipped, false),
+        ],
+    ];
+    chunked_sum_result(&estimates, &records)
+}
+
+#[test]
+fn result_treatments() {
+    let result = result();
+    assert_eq!(result.treatments().len(), 6);
+
+    let treatment = &result.treatments()[4];
+    assert_eq!((treatment.t, treatment.i, treatment.a), (5, 2, 2));
+    assert_eq!(treatment.alg, &Chunk(4));
+    assert_eq!(treatment.estimate, Some(200.0));
+    assert_eq!(treatment.rank, Some(1));
+    assert_eq!(treatment.validation, Validation::Matched);
+
+    let skipped = &result.treatments()[5];
+    assert_eq!(skipped.status, TreatmentStatus::Skipped);
+    assert_eq!(skipped.rank, None);
+    assert_eq!(skipped.validation, Validation::NotExecuted);
+
+    assert_eq!(result.treatments()[2].validation, Validation::Unchecked);
+    assert!(result.treatments_for(&Len(7)).is_empty());
+}
+
+#[test]
+fn result_best_for_input() {
+    let result = result();
+
+    assert_eq!(result.best_for(&Len(10)), Some(&Chunk(1)));
+    assert_eq!(
+        result.ranking_for(&Len(10)),
+        [&Chunk(1), &Chunk(4), &Chunk(64)]
+    );
+
+    assert_eq!(result.best_for(&Len(1000)), Some(&Chunk(4)));
+    assert_eq!(result.ranking_for(&Len(1000)), [&Chunk(4), &Chunk(1)]);
+
+    assert_eq!(result.best_for(&Len(7)), None);
+}
+
+#[test]
+fn result_best_overall() {
+    let result = result();
+
+    // relative times: chunk 1 => 1 & 2, chunk 4 => 2 & 1, chunk 64 is not measured on all inputs
+    // ties are broken by the order of the variants
+    assert_eq!(result.overall_ranking(), [&Chunk(1), &Chunk(4)]);
+    assert_eq!(result.best_overall(), Some(&Chunk(1)));
+}
+
+#[test]
+fn result_without_estimates() {
+    let estimates = vec![vec![None; 3]; 2];
+    let records = vec![vec![TreatmentRecord::skipped(); 3]; 2];
+    let result =
+        ExperimentResult::<ChunkedSum>::new("chunks", "ns", &INPUTS, &ALGS, &estimates, &records);
+
+    assert_eq!(result.best_for(&Len(10)), None);
+    assert_eq!(result.best_overall(), None);
+}
@@ -0,0 +1,100 @@
+use crate::experiment_sealed::ExperimentSealed;
+use crate::record::TreatmentRecord;
+use crate::summary::try_summarize;
+use crate::tests::fixtures::{Len, Sum, read_summary_csv, summary_cell};
+use crate::{CsvFormat, Experiment, derive_seed};
+use criterion::measurement::WallTime;
+
+struct SeededExperiment(u64);
+
+impl Experiment for SeededExperiment {
+    type InputFactors = Len;
+
+    type AlgFactors = Sum;
+
+    type Input = Vec<u64>;
+
+    type Output = u64;
+
+    fn master_seed(&self) -> u64 {
+        self.0
+    }
+
+    fn input(&mut self, input_variant: &Self::InputFactors) -> Self::Input {
+        vec![self.input_seed(input_variant); input_variant.0]
+    }
+
+    fn execute(&mut self, _: &Self::AlgFactors, input: &Self::Input) -> Self::Output {
+        input.iter().sum()
+    }
+
+    fn csv_format(&self) -> CsvFormat {
+        CsvFormat {
+            long_format: true,
+            ..Default::default()
+        }
+    }
+}
+
+#[test]
+fn derive_seed_is_stable() {
+    // seeds must not change across versions, otherwise experiments cannot be reproduced
+    assert_eq!(derive_seed(0, ""), 0xC381_7C01_6BA4_FF30);
+    assert_eq!(derive_seed(42, "len:1024"), derive_seed(42, "len:1024"));
+}
+
+#[test]
+fn derive_seed_distinguishes_keys_and_master_seeds() {
+    let seeds = [
+        derive_seed(0, "len:1"),
+        derive_seed(0, "len:2"),
+        derive_seed(0, "len:10"),
+        derive_seed(1, "len:1"),
+        derive_seed(1, "len:2"),
+    ];
+    for (i, x) in seeds.iter().enumerate() {
+        assert!(!seeds[i + 1..].contains(x));
+    }
+}
+
+#[test]
+fn input_seed_from_master_seed_and_key() {
+    let exp = SeededExperiment(7);
+    assert_eq!(exp.input_seed(&Len(3)), derive_seed(7, "len:3"));
+    assert_ne!(exp.input_seed(&Len(3)), exp.input_seed(&Len(4)));
+    assert_ne!(
+        exp.input_seed(&Len(3)),
+        SeededExperiment(8).input_seed(&Len(3))
+    );
+
+    let mut exp = SeededExperiment(7);
+    assert_eq!(exp.input(&Len(3)), exp.input(&Len(3)));
+}
+
+#[test]
+fn summary_csv_records_master_seed() {
+    let name = "orx_criterion_test_master_seed";
+    let exp = SeededExperiment(7);
+    let input_levels = [Len(1), Len(2)];
+    let records = vec![vec![TreatmentRecord::default()]; 2];
+
+    try_summarize::<_, WallTime>(&exp, name, &input_levels, &[Sum], &records)
+        .expect("summary is created");
+
+    let summary = read_summary_csv(name);
+    assert_eq!(
+        summary[0][..6],
+        ["t", "i", "a", "len", "alg", "master_seed"]
+    );
+    assert_eq!(summary_cell(&summary, 1, "master_seed"), "7");
+    assert_eq!(summary_cell(&summary, 2, "master_seed"), "7");
+
+    let long = std::fs::read_to_string(exp.summary_long_csv_path(name))
+        .expect("long summary csv is created");
+    let mut lines = long.lines();
+    assert_eq!(
+        lines.next(),
+        Some("t,i,a,len,alg,master_seed,statistic,value")
+    );
+    assert!(lines.all(|x| x.split(',').nth(5) == Some("7")));
+}
@@ -0,0 +1,218 @@
+use crate::estimate::Estimate;
+use crate::experiment_sealed::ExperimentSealed;
+use crate::record::{TreatmentRecord, TreatmentStatus};
+use crate::summary_json::{Json, summary_json};
+use crate::tests::fixtures::{
+    Len, Sum, SumExperiment, quick_criterion, read_summary_csv, summary_cell,
+};
+use crate::{Experiment, ExperimentResult, Factors};
+
+struct Sep(&'static str);
+
+impl Factors for Sep {
+    fn factor_names() -> Vec<&'static str> {
+        vec!["separator"]
+    }
+
+    fn factor_levels(&self) -> Vec<String> {
+        vec![self.0.to_string()]
+    }
+}
+
+struct Join;
+
+impl Experiment for Join {
+    type InputFactors = Len;
+
+    type AlgFactors = Sep;
+
+    type Input = Vec<String>;
+
+    type Output = String;
+
+    fn input(&mut self, input_variant: &Self::InputFactors) -> Self::Input {
+        (0..input_variant.0).map(|x| x.to_string()).collect()
+    }
+
+    fn execute(&mut self, alg_variant: &Self::AlgFactors, input: &Self::Input) -> Self::Output {
+        input.join(alg_variant.0)
+    }
+}
+
+fn to_string(json: &Json) -> String {
+    let mut out = String::new();
+    json.write(&mut out, 0);
+    out
+}
+
+#[test]
+fn write_json_values() {
+    assert_eq!(to_string(&Json::Null), "null");
+    assert_eq!(to_string(&Json::Int(42)), "42");
+    assert_eq!(to_string(&Json::Number(1.5)), "1.5");
+    assert_eq!(to_string(&Json::Number(3.0)), "3.0");
+    assert_eq!(to_string(&Json::number(Some(f64::NAN))), "null");
+    assert_eq!(to_string(&Json::number(None)), "null");
+    assert_eq!(to_string(&Json::Array(vec![])), "[]");
+    assert_eq!(to_string(&Json::object([])), "{}");
+}
+
+#[test]
+fn write_escaped_json_strings() {
+    let json = Json::String("(\"a\", 'b')\\\n\t\u{1}".to_string());
+    assert_eq!(to_string(&json), r#""(\"a\", 'b')\\\n\t\u0001""#);
+}
+
+#[test]
+fn write_nested_json() {
+    let json = Json::object([
+        ("name", Json::String("x".to_string())),
+        ("values", Json::Array(vec![Json::Int(1), Json::Null])),
+    ]);
+    let expected = "{\n  \"name\": \"x\",\n  \"values\": [\n    1,\n    null\n  ]\n}";
+    assert_eq!(to_string(&json), expected);
+}
+
+#[test]
+fn summary_json_of_experiment() {
+    let inputs = [Len(8)];
+    let algs = [Sep(","), Sep("\"")];
+    let estimates = vec![vec![Some(10.0), None]];
+    let records = vec![vec![
+        TreatmentRecord {
+            validated: true,
+            metrics: vec![("len", 15.0)],
+            ..Default::default()
+        },
+        TreatmentRecord {
+            status: TreatmentStatus::TimedOut,
+            ..Default::default()
+        },
+    ]];
+    let result = ExperimentResult::new("join", "ns", &inputs, &algs, &estimates, &records);
+    let intervals = vec![vec![
+        Some(Estimate {
+            statistic: "slope",
+            point: 10.0,
+            lower_bound: 9.0,
+            upper_bound: 11.0,
+            confidence_level: 0.95,
+        }),
+        None,
+    ]];
+
+    let json = to_string(&summary_json(&Join, &result, &intervals));
+    let expected = r#"{
+  "schema_version": 1,
+  "name": "join",
+  "time_unit": "ns",
+  "master_seed": 0,
+  "num_replicates": 1,
+  "treatment_order": {
+    "kind": "Canonical",
+    "seed": null
+  },
+  "num_inputs": 1,
+  "num_variants": 2,
+  "input_factors": [
+    {
+      "name": "len",
+      "short_name": "l",
+      "levels": [
+        "8"
+      ],
+      "levels_short": [
+        "8"
+      ]
+    }
+  ],
+  "alg_factors": [
+    {
+      "name": "separator",
+      "short_name": "separator",
+      "levels": [
+        ",",
+        "\""
+      ],
+      "levels_short": [
+        ",",
+        "\""
+      ]
+    }
+  ],
+  "treatments": [
+    {
+      "t": 1,
+      "i": 1,
+      "a": 1,
+      "key_long": "len:8/separator:,",
+      "key_short": "l:8/separator:,",
+      "input_levels": {
+        "len": "8"
+      },
+      "alg_levels": {
+        "separator": ","
+      },
+      "status": "Measured",
+      "validation": "Matched",
+      "estimate": {
+        "statistic": "slope",
+        "point": 10.0,
+        "lower_bound": 9.0,
+        "upper_bound": 11.0,
+        "confidence_level": 0.95
+      },
+      "rank": 1,
+      "metrics": {
+        "len": 15.0
+      }
+    },
+    {
+      "t": 2,
+      "i": 1,
+      "a": 2,
+      "key_long": "len:8/separator:\"",
+      "key_short": "l:8/separator:\"",
+      "input_levels": {
+        "len": "8"
+      },
+      "alg_levels": {
+        "separator": "\""
+      },
+      "status": "TimedOut",
+      "validation": "Unchecked",
+      "estimate": null,
+      "rank": null,
+      "metrics": {}
+    }
+  ]
+}"#;
+    assert_eq!(json, expected);
+}
+
+#[test]
+fn treatments_excluded_by_filter_are_not_run() {
+    let name = "orx_criterion_test_not_run";
+    let input_levels = [Len(10), Len(20)];
+    let mut c = quick_criterion().with_filter("l:10/");
+
+    let result = SumExperiment
+        .try_bench(&mut c, name, &input_levels, &[Sum])
+        .expect("experiment is reported");
+
+    let treatments = result.treatments();
+    assert_eq!(treatments[0].status, TreatmentStatus::Measured);
+    assert!(treatments[0].estimate.is_some());
+    assert_eq!(treatments[1].status, TreatmentStatus::NotRun);
+    assert_eq!(treatments[1].estimate, None);
+
+    let summary = read_summary_csv(name);
+    let time = summary_cell(&summary, 1, "Time (ns)");
+    assert!(time.parse::<f64>().is_ok(), "{time} is not measured");
+    assert_eq!(summary_cell(&summary, 2, "Time (ns)"), "NOT RUN");
+
+    let json = std::fs::read_to_string(SumExperiment.summary_json_path(name))
+        .expect("json summary is created");
+    assert_eq!(json.matches("\"status\": \"Measured\"").count(), 1);
+    assert_eq!(json.matches("\"status\": \"NotRun\"").count(), 1);
+}
@@ -0,0 +1,107 @@
+use crate::tests::fixtures::{Len, Sum, quick_criterion};
+use crate::throughput::{Processed, format_rate, processed_kinds};
+use crate::{Error, Experiment};
+use criterion::Throughput;
+use criterion::measurement::WallTime;
+
+/// Defines the throughput only for the inputs with at least 100 elements.
+struct PartialThroughputExperiment;
+
+impl Experiment for PartialThroughputExperiment {
+    type InputFactors = Len;
+
+    type AlgFactors = Sum;
+
+    type Input = Vec<usize>;
+
+    type Output = usize;
+
+    fn input(&mut self, input_variant: &Self::InputFactors) -> Self::Input {
+        (0..input_variant.0).collect()
+    }
+
+    fn execute(&mut self, _: &Self::AlgFactors, input: &Self::Input) -> Self::Output {
+        input.iter().sum()
+    }
+
+    fn throughput(&self, input_variant: &Self::InputFactors) -> Option<Throughput> {
+        match input_variant.0 {
+            len if len >= 100 => Some(Throughput::Elements(len as u64)),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn throughput_processed_kinds() {
+    let throughputs = [
+        None,
+        Some(Throughput::Bytes(8)),
+        Some(Throughput::Elements(2)),
+    ];
+    assert_eq!(
+        processed_kinds(&throughputs),
+        [Processed::Elements, Processed::Bytes]
+    );
+
+    let throughputs = [Some(Throughput::ElementsAndBytes {
+        elements: 2,
+        bytes: 8,
+    })];
+    assert_eq!(
+        processed_kinds(&throughputs),
+        [Processed::Elements, Processed::Bytes]
+    );
+
+    assert!(processed_kinds(&[None, None]).is_empty());
+}
+
+#[test]
+fn throughput_rate() {
+    let elements = Throughput::Elements(1000);
+    assert_eq!(Processed::Elements.title::<WallTime>(), "Elements/s");
+    assert_eq!(
+        Processed::Elements.rate::<WallTime>(Some(&elements), Some(500.0)),
+        Some(2e9)
+    );
+    assert_eq!(
+        Processed::Bytes.rate::<WallTime>(Some(&elements), Some(500.0)),
+        None
+    );
+    assert_eq!(
+        Processed::Elements.rate::<WallTime>(Some(&elements), None),
+        None
+    );
+    assert_eq!(
+        Processed::Elements.rate::<WallTime>(None, Some(500.0)),
+        None
+    );
+}
+
+#[test]
+fn throughput_format_rate() {
+    assert_eq!(format_rate(Some(2e9)), "2.00 G");
+    assert_eq!(format_rate(Some(1250.0)), "1.25 K");
+    assert_eq!(format_rate(Some(12.5)), "12.50");
+    assert_eq!(format_rate(None), "NA");
+}
+
+#[test]
+fn throughput_for_all_or_none_of_inputs() {
+    let name = "orx_criterion_test_partial_throughput";
+    let mut c = quick_criterion();
+    let result = PartialThroughputExperiment.try_bench(&mut c, name, &[Len(100), Len(10)], &[Sum]);
+
+    let Err(error) = result else {
+        unreachable!("partial throughput is an error")
+    };
+    assert!(matches!(
+        &error,
+        Error::InconsistentThroughput { defined, missing }
+            if defined == "len:100" && missing == "len:10"
+    ));
+    assert_eq!(
+        error.to_string(),
+        "throughput must be defined for all or none of the inputs; it is defined for len:100 but not for len:10"
+    );
+}
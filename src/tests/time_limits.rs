@@ -0,0 +1,101 @@
+use crate::tests::fixtures::{Len, Sum, quick_criterion, read_summary_csv, summary_cell};
+use crate::{Experiment, TreatmentConfig, TreatmentStatus};
+use std::time::Duration;
+
+/// Sums up the input, sleeping for 5 ms on inputs longer than 10 elements, and sleeping for 250 ms after each
+/// treatment when the experiment has a time budget.
+struct SlowExperiment {
+    time_limit: Option<Duration>,
+    time_budget: Option<Duration>,
+}
+
+impl Experiment for SlowExperiment {
+    type InputFactors = Len;
+
+    type AlgFactors = Sum;
+
+    type Input = Vec<usize>;
+
+    type Output = usize;
+
+    fn input(&mut self, input_variant: &Self::InputFactors) -> Self::Input {
+        (0..input_variant.0).collect()
+    }
+
+    fn execute(&mut self, _: &Self::AlgFactors, input: &Self::Input) -> Self::Output {
+        if input.len() > 10 {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        input.iter().sum()
+    }
+
+    fn configure(&self, _: &Self::InputFactors, _: &Self::AlgFactors) -> TreatmentConfig {
+        TreatmentConfig {
+            time_limit: self.time_limit,
+            ..Default::default()
+        }
+    }
+
+    fn time_budget(&self) -> Option<Duration> {
+        self.time_budget
+    }
+
+    fn after_treatment(&mut self, _: &Self::InputFactors, _: &Self::AlgFactors) -> Option<String> {
+        if self.time_budget.is_some() {
+            std::thread::sleep(Duration::from_millis(250));
+        }
+        None
+    }
+}
+
+#[test]
+fn time_limit_exceeded_by_pilot() {
+    let name = "orx_criterion_test_time_limit";
+    let input_levels = [Len(10), Len(20)];
+    let mut c = quick_criterion();
+
+    let mut exp = SlowExperiment {
+        time_limit: Some(Duration::from_millis(1)),
+        time_budget: None,
+    };
+    let result = exp
+        .try_bench(&mut c, name, &input_levels, &[Sum])
+        .expect("experiment is reported");
+
+    let treatments = result.treatments();
+    assert_eq!(treatments[0].status, TreatmentStatus::Measured);
+    assert!(treatments[0].estimate.is_some());
+    assert_eq!(treatments[1].status, TreatmentStatus::TimedOut);
+    assert_eq!(treatments[1].estimate, None);
+
+    let summary = read_summary_csv(name);
+    let time = summary_cell(&summary, 1, "Time (ns)");
+    assert!(time.parse::<f64>().is_ok(), "{time} is not measured");
+    assert_eq!(summary_cell(&summary, 2, "Time (ns)"), "TIMEOUT");
+}
+
+#[test]
+fn time_budget_exhausted() {
+    let name = "orx_criterion_test_time_budget";
+    let input_levels = [Len(10), Len(20)];
+    let mut c = quick_criterion();
+
+    let mut exp = SlowExperiment {
+        time_limit: None,
+        time_budget: Some(Duration::from_millis(200)),
+    };
+    let result = exp
+        .try_bench(&mut c, name, &input_levels, &[Sum])
+        .expect("experiment is reported");
+
+    let treatments = result.treatments();
+    assert_eq!(treatments[0].status, TreatmentStatus::Measured);
+    assert!(treatments[0].estimate.is_some());
+    assert_eq!(treatments[1].status, TreatmentStatus::Skipped);
+    assert_eq!(treatments[1].estimate, None);
+
+    let summary = read_summary_csv(name);
+    let time = summary_cell(&summary, 1, "Time (ns)");
+    assert!(time.parse::<f64>().is_ok(), "{time} is not measured");
+    assert_eq!(summary_cell(&summary, 2, "Time (ns)"), "SKIPPED");
+}
@@ -0,0 +1,50 @@
+use crate::Tolerance;
+
+#[test]
+fn tolerance_absolute() {
+    let tolerance = Tolerance::absolute(0.5);
+
+    assert!(tolerance.check(10.0, 10.4).is_ok());
+    assert!(tolerance.check(10.0, 9.6).is_ok());
+    assert!(tolerance.check(10.0, 10.6).is_err());
+    assert!(tolerance.check(1e9, 1e9 + 1.0).is_err());
+}
+
+#[test]
+fn tolerance_relative() {
+    let tolerance = Tolerance::relative(0.01);
+
+    assert!(tolerance.check(1e9, 1e9 + 1.0).is_ok());
+    assert!(tolerance.check(100.0, 100.9).is_ok());
+    assert!(tolerance.check(100.0, 102.0).is_err());
+    assert!(tolerance.check(0.0, 1e-12).is_err());
+}
+
+#[test]
+fn tolerance_special_values() {
+    let tolerance = Tolerance::new(1e-6, 1e-6);
+
+    assert!(tolerance.check(f64::NAN, f64::NAN).is_ok());
+    assert!(tolerance.check(f64::NAN, 1.0).is_err());
+    assert!(tolerance.check(f64::INFINITY, f64::INFINITY).is_ok());
+    assert!(tolerance.check(f64::INFINITY, f64::NEG_INFINITY).is_err());
+    assert!(tolerance.check(f64::INFINITY, f64::MAX).is_err());
+}
+
+#[test]
+fn tolerance_slice() {
+    let tolerance = Tolerance::absolute(0.1);
+
+    assert!(tolerance.check_slice(&[], &[]).is_ok());
+    assert!(tolerance.check_slice(&[1.0, 2.0], &[1.05, 1.95]).is_ok());
+
+    let error = tolerance
+        .check_slice(&[1.0, 2.0], &[1.0])
+        .expect_err("must not match");
+    assert!(error.contains("lengths differ"));
+
+    let error = tolerance
+        .check_slice(&[1.0, 2.0, 3.0], &[1.0, 2.5, 3.5])
+        .expect_err("must not match");
+    assert!(error.starts_with("at index 1;"));
+}
@@ -0,0 +1,106 @@
+use crate::{Error, Experiment, ExperimentMeasurement, Factors};
+use criterion::Throughput;
+
+/// Kind of the amount processed by each execution of an input, as defined by criterion's [`Throughput`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Processed {
+    Elements,
+    Bytes,
+    Bits,
+}
+
+impl Processed {
+    const ALL: [Processed; 3] = [Processed::Elements, Processed::Bytes, Processed::Bits];
+
+    /// Amount of this kind processed per execution with the given `throughput`, if it is of this kind.
+    fn amount(self, throughput: &Throughput) -> Option<f64> {
+        match (self, throughput) {
+            (Processed::Elements, Throughput::Elements(x)) => Some(*x as f64),
+            (Processed::Elements, Throughput::ElementsAndBytes { elements, .. }) => {
+                Some(*elements as f64)
+            }
+            (Processed::Bytes, Throughput::Bytes(x) | Throughput::BytesDecimal(x)) => {
+                Some(*x as f64)
+            }
+            (Processed::Bytes, Throughput::ElementsAndBytes { bytes, .. }) => Some(*bytes as f64),
+            (Processed::Bits, Throughput::Bits(x)) => Some(*x as f64),
+            _ => None,
+        }
+    }
+
+    /// Title of the summary column of the throughput of this kind; such as "Elements/s".
+    ///
+    /// Throughput is reported per second when the measurement is in nanoseconds, and per unit of the
+    /// measurement otherwise.
+    pub fn title<M: ExperimentMeasurement>(self) -> String {
+        let name = match self {
+            Processed::Elements => "Elements",
+            Processed::Bytes => "Bytes",
+            Processed::Bits => "Bits",
+        };
+        match M::unit() {
+            "ns" => format!("{name}/s"),
+            unit => format!("{name}/{unit}"),
+        }
+    }
+
+    /// Throughput of this kind of the treatment with the given input `throughput` and measured `time`;
+    /// None if either is missing or the throughput is not of this kind.
+    pub fn rate<M: ExperimentMeasurement>(
+        self,
+        throughput: Option<&Throughput>,
+        time: Option<f64>,
+    ) -> Option<f64> {
+        let amount = self.amount(throughput?)?;
+        let time = time.filter(|x| *x > 0.0)?;
+        match M::unit() {
+            "ns" => Some(amount * 1e9 / time),
+            _ => Some(amount / time),
+        }
+    }
+}
+
+/// Throughputs of the inputs defined by [`throughput`]; returns an error if the throughput is defined for some
+/// of the inputs but not for the others, since criterion would then keep the throughput of a previous input.
+///
+/// [`throughput`]: crate::Experiment::throughput
+pub(crate) fn input_throughputs<E: Experiment>(
+    exp: &E,
+    input_levels: &[E::InputFactors],
+) -> Result<Vec<Option<Throughput>>, Error> {
+    let throughputs: Vec<_> = input_levels.iter().map(|x| exp.throughput(x)).collect();
+    let defined = throughputs.iter().position(|x| x.is_some());
+    let missing = throughputs.iter().position(|x| x.is_none());
+    match (defined, missing) {
+        (Some(defined), Some(missing)) => Err(Error::InconsistentThroughput {
+            defined: input_levels[defined].key_long(),
+            missing: input_levels[missing].key_long(),
+        }),
+        _ => Ok(throughputs),
+    }
+}
+
+/// Kinds of throughput defined for any of the inputs.
+pub(crate) fn processed_kinds(throughputs: &[Option<Throughput>]) -> Vec<Processed> {
+    Processed::ALL
+        .into_iter()
+        .filter(|kind| {
+            throughputs
+                .iter()
+                .flatten()
+                .any(|t| kind.amount(t).is_some())
+        })
+        .collect()
+}
+
+/// String representation of a throughput to be used in the summary table, scaled by SI prefixes; such as "1.25 G".
+pub(crate) fn format_rate(value: Option<f64>) -> String {
+    const PREFIXES: [(f64, &str); 4] = [(1e12, "T"), (1e9, "G"), (1e6, "M"), (1e3, "K")];
+    match value {
+        Some(x) => match PREFIXES.iter().find(|(scale, _)| x >= *scale) {
+            Some((scale, prefix)) => format!("{:.2} {prefix}", x / scale),
+            None => format!("{x:.2}"),
+        },
+        None => "NA".to_string(),
+    }
+}
@@ -0,0 +1,100 @@
+/// Absolute and relative tolerances to compare floating-point outputs.
+///
+/// Two values `expected` and `actual` are considered to be equal when
+/// `|expected - actual| <= max(absolute, relative * max(|expected|, |actual|))`.
+///
+/// It is mainly used to implement [`outputs_match`] for experiments with approximate outputs,
+/// such as costs computed by heuristics or floating-point results.
+///
+/// [`outputs_match`]: crate::Experiment::outputs_match
+///
+/// # Examples
+///
+/// ```
+/// use orx_criterion::*;
+///
+/// let tolerance = Tolerance::relative(1e-3);
+///
+/// assert!(tolerance.check(1000.0, 1000.5).is_ok());
+/// assert!(tolerance.check(1000.0, 1002.0).is_err());
+///
+/// let tolerance = Tolerance::absolute(0.1);
+///
+/// assert!(tolerance.check_slice(&[1.0, 2.0], &[1.05, 1.95]).is_ok());
+/// assert!(tolerance.check_slice(&[1.0, 2.0], &[1.05, 2.15]).is_err());
+/// assert!(tolerance.check_slice(&[1.0, 2.0], &[1.0]).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    /// Maximum allowed absolute difference between the expected and actual values.
+    pub absolute: f64,
+    /// Maximum allowed difference relative to the larger magnitude of the expected and actual values.
+    pub relative: f64,
+}
+
+impl Tolerance {
+    /// Creates a tolerance with the given `absolute` and `relative` bounds.
+    pub fn new(absolute: f64, relative: f64) -> Self {
+        Self { absolute, relative }
+    }
+
+    /// Creates a tolerance which only allows an `absolute` difference.
+    pub fn absolute(absolute: f64) -> Self {
+        Self::new(absolute, 0.0)
+    }
+
+    /// Creates a tolerance which only allows a `relative` difference.
+    pub fn relative(relative: f64) -> Self {
+        Self::new(0.0, relative)
+    }
+
+    /// Returns whether or not the `expected` and `actual` values are equal within this tolerance.
+    ///
+    /// Two NaN values are considered to be equal, while a NaN is never equal to a number.
+    pub fn is_match(&self, expected: f64, actual: f64) -> bool {
+        match (expected.is_nan(), actual.is_nan()) {
+            (true, true) => true,
+            (false, false) if expected.is_infinite() || actual.is_infinite() => {
+                expected.to_bits() == actual.to_bits()
+            }
+            (false, false) => {
+                let diff = (expected - actual).abs();
+                let magnitude = expected.abs().max(actual.abs());
+                diff <= self.absolute.max(self.relative * magnitude)
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns `Ok(())` if the `expected` and `actual` values are equal within this tolerance;
+    /// an error message describing the difference otherwise.
+    pub fn check(&self, expected: f64, actual: f64) -> Result<(), String> {
+        match self.is_match(expected, actual) {
+            true => Ok(()),
+            false => Err(format!(
+                "expected: {expected:?}, actual: {actual:?}, difference: {:?} exceeds {self:?}",
+                (expected - actual).abs()
+            )),
+        }
+    }
+
+    /// Returns `Ok(())` if the `expected` and `actual` slices have the same length and all their
+    /// elements are pairwise equal within this tolerance; an error message describing the first
+    /// difference otherwise.
+    pub fn check_slice(&self, expected: &[f64], actual: &[f64]) -> Result<(), String> {
+        if expected.len() != actual.len() {
+            return Err(format!(
+                "lengths differ; expected: {}, actual: {}",
+                expected.len(),
+                actual.len()
+            ));
+        }
+
+        for (i, (e, a)) in expected.iter().zip(actual).enumerate() {
+            self.check(*e, *a)
+                .map_err(|msg| format!("at index {i}; {msg}"))?;
+        }
+
+        Ok(())
+    }
+}
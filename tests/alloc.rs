@@ -0,0 +1,88 @@
+// The counting allocator is registered as the global allocator of this test binary, which is kept separate
+// from the unit tests since the allocation counters are global and shared by all threads.
+
+use orx_criterion::{AllocationStats, CountingAllocator, PhaseTimer, measure_allocations};
+use std::alloc::System;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator::new(System);
+
+#[test]
+fn count_allocations() {
+    // single allocation
+    let (vec, stats) = measure_allocations(|| {
+        let mut vec = Vec::<u64>::with_capacity(4);
+        vec.extend([1, 2, 3, 4]);
+        vec
+    });
+    assert_eq!(vec, [1, 2, 3, 4]);
+    assert_eq!(
+        stats,
+        Some(AllocationStats {
+            count: 1,
+            bytes: 32,
+            peak_bytes: 32,
+        })
+    );
+
+    // released memory does not count towards the peak
+    let (_, stats) = measure_allocations(|| {
+        let first = Vec::<u8>::with_capacity(64);
+        drop(first);
+        Vec::<u8>::with_capacity(16)
+    });
+    assert_eq!(
+        stats,
+        Some(AllocationStats {
+            count: 2,
+            bytes: 80,
+            peak_bytes: 64,
+        })
+    );
+
+    // reallocation counts as an allocation of the new size
+    let (_, stats) = measure_allocations(|| {
+        let mut vec = Vec::<u8>::with_capacity(8);
+        vec.reserve_exact(24); // grows the capacity of the empty vec to 24
+        vec
+    });
+    assert_eq!(
+        stats,
+        Some(AllocationStats {
+            count: 2,
+            bytes: 32,
+            peak_bytes: 24,
+        })
+    );
+
+    // memory allocated before the call and freed during the call does not lower the peak
+    let input = Vec::<u8>::with_capacity(64);
+    let (_, stats) = measure_allocations(move || {
+        drop(input);
+        Vec::<u8>::with_capacity(16)
+    });
+    assert_eq!(
+        stats,
+        Some(AllocationStats {
+            count: 1,
+            bytes: 16,
+            peak_bytes: 16,
+        })
+    );
+
+    // default phase timer is disabled and does not allocate
+    let (_, stats) = measure_allocations(|| {
+        let mut timer = PhaseTimer::default();
+        timer.start("build");
+        timer.start("query");
+        timer.stop();
+    });
+    assert_eq!(
+        stats,
+        Some(AllocationStats {
+            count: 0,
+            bytes: 0,
+            peak_bytes: 0,
+        })
+    );
+}